@@ -1,3 +1,11 @@
+pub mod avl_tree;
+pub mod binary_search_tree;
+pub mod binary_tree;
 pub mod doubly_linked_list;
+pub mod priority_queue;
 pub mod queue;
+pub mod rooted_tree;
+pub mod scheduler;
 pub mod stack;
+pub mod treap;
+pub mod union_find;
@@ -0,0 +1,466 @@
+use crate::search::dictionary::Dictionary;
+use std::{
+    cmp::Ordering,
+    ops::{Bound, RangeBounds},
+};
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    /// Number of nodes on the longest path down to a leaf, counting this one.
+    height: usize,
+    /// Number of nodes in this subtree.
+    size: usize,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> Node<T> {
+    fn new(value: T) -> Box<Self> {
+        Box::new(Node {
+            value,
+            height: 1,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+fn height<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |n| n.height)
+}
+
+fn size<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |n| n.size)
+}
+
+fn rotate_left<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut r = node.right.take().unwrap();
+    node.right = r.left.take();
+    node.update();
+    r.left = Some(node);
+    r.update();
+    r
+}
+
+fn rotate_right<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut l = node.left.take().unwrap();
+    node.left = l.right.take();
+    node.update();
+    l.right = Some(node);
+    l.update();
+    l
+}
+
+/// Restores the AVL invariant at `node` when its subtrees differ in height by
+/// at most 2.
+fn balance<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    node.update();
+    let (lh, rh) = (height(&node.left), height(&node.right));
+    if lh > rh + 1 {
+        let left = node.left.take().unwrap();
+        node.left = Some(if height(&left.left) < height(&left.right) {
+            rotate_left(left)
+        } else {
+            left
+        });
+        node = rotate_right(node);
+    } else if rh > lh + 1 {
+        let right = node.right.take().unwrap();
+        node.right = Some(if height(&right.right) < height(&right.left) {
+            rotate_right(right)
+        } else {
+            right
+        });
+        node = rotate_left(node);
+    }
+    node
+}
+
+/// Joins `left`, `mid` and `right` where the values are in that order, descending
+/// the taller tree until the heights are close enough to hang the other under `mid`.
+fn join<T>(left: Link<T>, mut mid: Box<Node<T>>, right: Link<T>) -> Box<Node<T>> {
+    let (lh, rh) = (height(&left), height(&right));
+    if lh > rh + 1 {
+        let mut l = left.unwrap();
+        l.right = Some(join(l.right.take(), mid, right));
+        balance(l)
+    } else if rh > lh + 1 {
+        let mut r = right.unwrap();
+        r.left = Some(join(left, mid, r.left.take()));
+        balance(r)
+    } else {
+        mid.left = left;
+        mid.right = right;
+        mid.update();
+        mid
+    }
+}
+
+/// Splits into the values less than `value` and the rest.
+fn split<T: Ord>(link: Link<T>, value: &T) -> (Link<T>, Link<T>) {
+    match link {
+        None => (None, None),
+        Some(mut node) => {
+            let (left, right) = (node.left.take(), node.right.take());
+            if node.value < *value {
+                let (l, r) = split(right, value);
+                (Some(join(left, node, l)), r)
+            } else {
+                let (l, r) = split(left, value);
+                (l, Some(join(r, node, right)))
+            }
+        }
+    }
+}
+
+/// Detaches the smallest node, returning it and what is left of the tree.
+fn pop_min<T>(mut node: Box<Node<T>>) -> (Box<Node<T>>, Link<T>) {
+    match node.left.take() {
+        None => {
+            let rest = node.right.take();
+            (node, rest)
+        }
+        Some(left) => {
+            let (min, rest) = pop_min(left);
+            node.left = rest;
+            (min, Some(balance(node)))
+        }
+    }
+}
+
+/// An AVL tree: a binary search tree whose subtrees differ in height by at most 1.
+#[derive(Debug, Clone)]
+pub struct AvlTree<T> {
+    root: Link<T>,
+}
+
+impl<T: Ord> AvlTree<T> {
+    pub fn new() -> Self {
+        AvlTree { root: None }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Number of nodes on the longest path from the root; 0 when empty.
+    pub fn height(&self) -> usize {
+        height(&self.root)
+    }
+
+    /// Removes `value` and returns it, or `None` if it is not in the tree.
+    pub fn delete(&mut self, value: &T) -> Option<T> {
+        fn inner<T: Ord>(link: Link<T>, value: &T) -> (Link<T>, Option<T>) {
+            let mut node = match link {
+                None => return (None, None),
+                Some(node) => node,
+            };
+            let removed = match value.cmp(&node.value) {
+                Ordering::Less => {
+                    let (left, removed) = inner(node.left.take(), value);
+                    node.left = left;
+                    removed
+                }
+                Ordering::Greater => {
+                    let (right, removed) = inner(node.right.take(), value);
+                    node.right = right;
+                    removed
+                }
+                Ordering::Equal => {
+                    // the successor takes the place of the removed node
+                    let (left, right) = (node.left.take(), node.right.take());
+                    let rest = match right {
+                        None => left,
+                        Some(right) => {
+                            let (mut min, right) = pop_min(right);
+                            min.left = left;
+                            min.right = right;
+                            Some(balance(min))
+                        }
+                    };
+                    return (rest, Some(node.value));
+                }
+            };
+            (Some(balance(node)), removed)
+        }
+
+        let (root, removed) = inner(self.root.take(), value);
+        self.root = root;
+        removed
+    }
+
+    /// Moves the values greater than or equal to `value` into a new tree.
+    pub fn split_off(&mut self, value: &T) -> Self {
+        let (l, r) = split(self.root.take(), value);
+        self.root = l;
+        AvlTree { root: r }
+    }
+
+    /// Appends `other`, whose values must all be greater than those in `self`.
+    /// Gives `other` back untouched if the two overlap.
+    pub fn merge(&mut self, other: Self) -> Result<(), Self> {
+        if let (Some(max), Some(min)) = (self.max(), other.min()) {
+            if max >= min {
+                return Err(other);
+            }
+        }
+        if let Some(right) = other.root {
+            let (min, right) = pop_min(right);
+            self.root = Some(join(self.root.take(), min, right));
+        }
+        Ok(())
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.select(0)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.select(self.len().checked_sub(1)?)
+    }
+
+    /// Number of values less than `value`.
+    pub fn rank(&self, value: &T) -> usize {
+        let mut rank = 0;
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            if node.value < *value {
+                rank += size(&node.left) + 1;
+                cur = &node.right;
+            } else {
+                cur = &node.left;
+            }
+        }
+        rank
+    }
+
+    /// The `k`-th smallest value, counting from 0.
+    pub fn select(&self, mut k: usize) -> Option<&T> {
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            let left = size(&node.left);
+            match k.cmp(&left) {
+                Ordering::Less => cur = &node.left,
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => {
+                    k -= left + 1;
+                    cur = &node.right;
+                }
+            }
+        }
+        None
+    }
+
+    /// Values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.root, Bound::Unbounded)
+    }
+
+    /// Values within `range` in ascending order.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> impl Iterator<Item = &T> {
+        let start = match range.start_bound() {
+            Bound::Included(x) => self.rank(x),
+            Bound::Excluded(x) => self.rank(x) + self.find(x) as usize,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(x) => self.rank(x) + self.find(x) as usize,
+            Bound::Excluded(x) => self.rank(x),
+            Bound::Unbounded => self.len(),
+        };
+        Iter::new(&self.root, range.start_bound()).take(end.saturating_sub(start))
+    }
+}
+
+impl<T: Ord> Default for AvlTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Dictionary<T> for AvlTree<T> {
+    /// Inserting a value already in the tree does nothing.
+    fn insert(&mut self, value: T) {
+        fn inner<T: Ord>(link: Link<T>, value: T) -> Box<Node<T>> {
+            let mut node = match link {
+                None => return Node::new(value),
+                Some(node) => node,
+            };
+            match value.cmp(&node.value) {
+                Ordering::Less => node.left = Some(inner(node.left.take(), value)),
+                Ordering::Greater => node.right = Some(inner(node.right.take(), value)),
+                Ordering::Equal => return node,
+            }
+            balance(node)
+        }
+
+        self.root = Some(inner(self.root.take(), value));
+    }
+
+    fn find(&self, value: &T) -> bool {
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            cur = match value.cmp(&node.value) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+}
+
+/// In-order iterator over an `AvlTree`.
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T: Ord> Iter<'a, T> {
+    /// Starts at the smallest value within `start`.
+    fn new(root: &'a Link<T>, start: Bound<&T>) -> Self {
+        let mut stack = vec![];
+        let mut cur = root.as_deref();
+        while let Some(node) = cur {
+            let in_range = match start {
+                Bound::Included(x) => node.value >= *x,
+                Bound::Excluded(x) => node.value > *x,
+                Bound::Unbounded => true,
+            };
+            if in_range {
+                stack.push(node);
+                cur = node.left.as_deref();
+            } else {
+                cur = node.right.as_deref();
+            }
+        }
+        Iter { stack }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let mut cur = node.right.as_deref();
+        while let Some(n) = cur {
+            self.stack.push(n);
+            cur = n.left.as_deref();
+        }
+        Some(&node.value)
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a AvlTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::XorShift;
+    use std::collections::BTreeSet;
+
+    /// Checks the ordering, heights, sizes and balance of every subtree.
+    fn check<T: Ord>(link: &Link<T>) -> (usize, usize) {
+        let node = match link {
+            None => return (0, 0),
+            Some(node) => node,
+        };
+        let (lh, ls) = check(&node.left);
+        let (rh, rs) = check(&node.right);
+        assert!(lh <= rh + 1 && rh <= lh + 1);
+        assert_eq!(node.height, 1 + lh.max(rh));
+        assert_eq!(node.size, 1 + ls + rs);
+        assert!(node.left.as_ref().is_none_or(|l| l.value < node.value));
+        assert!(node.right.as_ref().is_none_or(|r| r.value > node.value));
+        (node.height, node.size)
+    }
+
+    #[test]
+    fn test_sorted_insert() {
+        let len = 100_000;
+        let mut tree = AvlTree::new();
+        for x in 0..len {
+            tree.insert(x);
+        }
+        check(&tree.root);
+        // at most 1.44 log2(n)
+        assert!(tree.height() <= 24);
+        for x in (0..len).step_by(2) {
+            assert_eq!(tree.delete(&x), Some(x));
+        }
+        check(&tree.root);
+        assert_eq!(tree.len(), len / 2);
+        assert_eq!(tree.select(100), Some(&201));
+        assert_eq!(tree.rank(&201), 100);
+    }
+
+    #[test]
+    fn test_split_merge() {
+        let mut tree = AvlTree::new();
+        for x in 0..1000 {
+            tree.insert(x);
+        }
+
+        let mut upper = tree.split_off(&10);
+        check(&tree.root);
+        check(&upper.root);
+        assert!(tree.iter().eq((0..10).collect::<Vec<_>>().iter()));
+        assert_eq!(upper.min(), Some(&10));
+        assert_eq!(upper.len(), 990);
+
+        upper.delete(&11);
+        let mut overlapping = AvlTree::new();
+        overlapping.insert(5);
+        let overlapping = tree.merge(overlapping).unwrap_err();
+        assert_eq!((tree.len(), overlapping.len()), (10, 1));
+        tree.merge(upper).unwrap();
+        check(&tree.root);
+        assert_eq!(tree.len(), 999);
+        assert!(tree.range(8..=12).eq([8, 9, 10, 12].iter()));
+        assert!(tree.range(995..).eq([995, 996, 997, 998, 999].iter()));
+    }
+
+    #[test]
+    fn test_against_btree_set() {
+        let mut rng = XorShift::new(13);
+        let mut tree = AvlTree::new();
+        let mut expected = BTreeSet::new();
+        for _ in 0..2000 {
+            let x = rng.below(128);
+            if rng.below(3) == 0 {
+                assert_eq!(tree.delete(&x), expected.take(&x));
+            } else {
+                tree.insert(x);
+                expected.insert(x);
+            }
+            check(&tree.root);
+            let y = x + rng.below(16);
+            assert_eq!(tree.len(), expected.len());
+            assert_eq!(tree.rank(&x), expected.range(..x).count());
+            assert_eq!(tree.select(x), expected.iter().nth(x));
+            assert!(tree.range(x..y).eq(expected.range(x..y)));
+        }
+        assert!(tree.iter().eq(expected.iter()));
+    }
+}
@@ -0,0 +1,419 @@
+use crate::search::dictionary::Dictionary;
+use itertools::Itertools;
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::Display,
+    io::{prelude::*, BufReader},
+};
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    parent: Option<usize>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// An unbalanced binary search tree holding distinct values.
+///
+/// Nodes live in a `Vec` and refer to each other by index; deleting a node moves
+/// the last one into its slot.
+#[derive(Debug, Clone)]
+pub struct BinarySearchTree<T> {
+    nodes: Vec<Node<T>>,
+    root: Option<usize>,
+}
+
+impl<T: Ord> BinarySearchTree<T> {
+    pub fn new() -> Self {
+        BinarySearchTree {
+            nodes: vec![],
+            root: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn find_node(&self, value: &T) -> Option<usize> {
+        let mut cur = self.root;
+        while let Some(id) = cur {
+            let node = &self.nodes[id];
+            cur = match value.cmp(&node.value) {
+                Ordering::Less => node.left,
+                Ordering::Greater => node.right,
+                Ordering::Equal => return Some(id),
+            };
+        }
+        None
+    }
+
+    fn min_node(&self, mut id: usize) -> usize {
+        while let Some(left) = self.nodes[id].left {
+            id = left;
+        }
+        id
+    }
+
+    fn max_node(&self, mut id: usize) -> usize {
+        while let Some(right) = self.nodes[id].right {
+            id = right;
+        }
+        id
+    }
+
+    /// Puts `v` where the subtree rooted at `u` hangs.
+    fn transplant(&mut self, u: usize, v: Option<usize>) {
+        let parent = self.nodes[u].parent;
+        match parent {
+            None => self.root = v,
+            Some(p) if self.nodes[p].left == Some(u) => self.nodes[p].left = v,
+            Some(p) => self.nodes[p].right = v,
+        }
+        if let Some(v) = v {
+            self.nodes[v].parent = parent;
+        }
+    }
+
+    /// Frees the slot of a detached node by moving the last node into it.
+    fn remove_slot(&mut self, id: usize) -> T {
+        let last = self.nodes.len() - 1;
+        if id != last {
+            let (parent, left, right) = {
+                let node = &self.nodes[last];
+                (node.parent, node.left, node.right)
+            };
+            match parent {
+                None => self.root = Some(id),
+                Some(p) if self.nodes[p].left == Some(last) => self.nodes[p].left = Some(id),
+                Some(p) => self.nodes[p].right = Some(id),
+            }
+            for c in left.into_iter().chain(right) {
+                self.nodes[c].parent = Some(id);
+            }
+        }
+        self.nodes.swap_remove(id).value
+    }
+
+    /// Removes `value` and returns it, or `None` if it is not in the tree.
+    ///
+    /// A node with two children takes the value of its successor, which is then
+    /// removed in its place.
+    pub fn delete(&mut self, value: &T) -> Option<T> {
+        let mut z = self.find_node(value)?;
+        let node = &self.nodes[z];
+        if let (Some(_), Some(right)) = (node.left, node.right) {
+            let y = self.min_node(right);
+            let (head, tail) = self.nodes.split_at_mut(y.max(z));
+            std::mem::swap(&mut head[y.min(z)].value, &mut tail[0].value);
+            z = y;
+        }
+
+        let node = &self.nodes[z];
+        let child = node.left.or(node.right);
+        self.transplant(z, child);
+        Some(self.remove_slot(z))
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.root.map(|r| &self.nodes[self.min_node(r)].value)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.root.map(|r| &self.nodes[self.max_node(r)].value)
+    }
+
+    /// The largest value less than `value`, which need not be in the tree.
+    pub fn predecessor(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut cur = self.root;
+        while let Some(id) = cur {
+            let node = &self.nodes[id];
+            if node.value < *value {
+                best = Some(&node.value);
+                cur = node.right;
+            } else {
+                cur = node.left;
+            }
+        }
+        best
+    }
+
+    /// The smallest value greater than `value`, which need not be in the tree.
+    pub fn successor(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut cur = self.root;
+        while let Some(id) = cur {
+            let node = &self.nodes[id];
+            if node.value > *value {
+                best = Some(&node.value);
+                cur = node.left;
+            } else {
+                cur = node.right;
+            }
+        }
+        best
+    }
+
+    /// Values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            nodes: &self.nodes,
+            stack: vec![],
+        };
+        iter.push_left(self.root);
+        iter
+    }
+
+    /// Values in preorder, walked with an explicit stack.
+    pub fn preorder(&self) -> Vec<&T> {
+        let mut result = Vec::with_capacity(self.len());
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id];
+            result.push(&node.value);
+            stack.extend(node.right);
+            stack.extend(node.left);
+        }
+        result
+    }
+}
+
+impl<T: Ord> Default for BinarySearchTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Dictionary<T> for BinarySearchTree<T> {
+    /// Inserting a value already in the tree does nothing.
+    fn insert(&mut self, value: T) {
+        let mut parent = None;
+        let mut cur = self.root;
+        while let Some(id) = cur {
+            let node = &self.nodes[id];
+            parent = cur;
+            cur = match value.cmp(&node.value) {
+                Ordering::Less => node.left,
+                Ordering::Greater => node.right,
+                Ordering::Equal => return,
+            };
+        }
+
+        let id = self.nodes.len();
+        match parent {
+            None => self.root = Some(id),
+            Some(p) if value < self.nodes[p].value => self.nodes[p].left = Some(id),
+            Some(p) => self.nodes[p].right = Some(id),
+        }
+        self.nodes.push(Node {
+            value,
+            parent,
+            left: None,
+            right: None,
+        });
+    }
+
+    fn find(&self, value: &T) -> bool {
+        self.find_node(value).is_some()
+    }
+}
+
+/// In-order iterator over a `BinarySearchTree`.
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    nodes: &'a [Node<T>],
+    stack: Vec<usize>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left(&mut self, mut cur: Option<usize>) {
+        while let Some(id) = cur {
+            self.stack.push(id);
+            cur = self.nodes[id].left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.stack.pop()?;
+        self.push_left(self.nodes[id].right);
+        Some(&self.nodes[id].value)
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a BinarySearchTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum Command<T> {
+    Insert(T),
+    Find(T),
+    Delete(T),
+    Print,
+}
+
+impl Command<isize> {
+    fn parse(line: &str) -> Result<Self, Box<dyn Error>> {
+        let mut line = line.split_whitespace();
+        let command = match (line.next(), line.next()) {
+            (Some("insert"), Some(x)) => Command::Insert(x.parse()?),
+            (Some("find"), Some(x)) => Command::Find(x.parse()?),
+            (Some("delete"), Some(x)) => Command::Delete(x.parse()?),
+            (Some("print"), None) => Command::Print,
+            (command, _) => return Err(format!("unknown command: {:?}", command).into()),
+        };
+        Ok(command)
+    }
+}
+
+/// Runs the commands; `find` prints `yes`/`no` and `print` prints the inorder and
+/// preorder walks, each value preceded by a space.
+pub fn compute<T: Ord + Clone + Display>(
+    commands: &[Command<T>],
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let mut tree = BinarySearchTree::new();
+    for command in commands {
+        match command {
+            Command::Insert(x) => tree.insert(x.clone()),
+            Command::Find(x) => writeln!(writer, "{}", if tree.find(x) { "yes" } else { "no" })?,
+            Command::Delete(x) => {
+                tree.delete(x);
+            }
+            Command::Print => {
+                writeln!(writer, " {}", tree.iter().join(" "))?;
+                writeln!(writer, " {}", tree.preorder().into_iter().join(" "))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn input_binary_search_tree(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let len: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut commands = Vec::with_capacity(len);
+    for line in lines.take(len) {
+        commands.push(Command::parse(&line?)?);
+    }
+
+    compute(&commands, writer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::XorShift;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test1() {
+        let input = [
+            "18",
+            "insert 8",
+            "insert 2",
+            "insert 3",
+            "insert 7",
+            "insert 22",
+            "insert 1",
+            "find 1",
+            "find 2",
+            "find 3",
+            "find 4",
+            "find 5",
+            "find 6",
+            "find 7",
+            "find 8",
+            "print",
+            "delete 3",
+            "delete 7",
+            "print",
+        ]
+        .join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_binary_search_tree(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "yes",
+                "yes",
+                "yes",
+                "no",
+                "no",
+                "no",
+                "yes",
+                "yes",
+                " 1 2 3 7 8 22",
+                " 8 2 1 3 7 22",
+                " 1 2 8 22",
+                " 8 2 1 22",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut tree = BinarySearchTree::new();
+        for x in &[30, 88, 12, 1, 20, 17, 25] {
+            tree.insert(*x);
+        }
+
+        // 12 has two children and is replaced by its successor 17
+        assert_eq!(tree.delete(&12), Some(12));
+        assert_eq!(tree.preorder(), vec![&30, &17, &1, &20, &25, &88]);
+        assert_eq!(tree.delete(&30), Some(30));
+        assert_eq!(tree.preorder(), vec![&88, &17, &1, &20, &25]);
+        assert_eq!(tree.delete(&30), None);
+
+        assert_eq!(tree.min(), Some(&1));
+        assert_eq!(tree.max(), Some(&88));
+        assert_eq!(tree.predecessor(&17), Some(&1));
+        assert_eq!(tree.successor(&21), Some(&25));
+        assert_eq!(tree.successor(&88), None);
+    }
+
+    #[test]
+    fn test_against_btree_set() {
+        let mut rng = XorShift::new(7);
+        let mut tree = BinarySearchTree::new();
+        let mut expected = BTreeSet::new();
+        for _ in 0..2000 {
+            let x = rng.below(64);
+            if rng.below(3) == 0 {
+                assert_eq!(tree.delete(&x), expected.take(&x));
+            } else {
+                tree.insert(x);
+                expected.insert(x);
+            }
+            assert_eq!(tree.len(), expected.len());
+            assert!(tree.iter().eq(expected.iter()));
+            assert_eq!(tree.find(&x), expected.contains(&x));
+            assert_eq!(tree.predecessor(&x), expected.range(..x).next_back());
+        }
+    }
+}
@@ -0,0 +1,554 @@
+use super::queue::{Queue, VecQueue};
+use super::rooted_tree::NodeType;
+use itertools::Itertools;
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{prelude::*, BufReader},
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BinaryTreeError {
+    NodeOutOfRange(usize),
+    MultipleParents(usize),
+    /// A node given as its own child.
+    SelfChild(usize),
+    /// The same node given as both the left and the right child.
+    DuplicateChild(usize),
+    /// The preorder and inorder sequences do not describe the same tree.
+    InvalidTraversal,
+}
+
+impl Display for BinaryTreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BinaryTreeError: {:?}", self)
+    }
+}
+
+impl Error for BinaryTreeError {}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Order {
+    Preorder,
+    Inorder,
+    Postorder,
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+struct Node {
+    parent: Option<usize>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// A binary tree with nodes `0..n`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BinaryTree {
+    nodes: Vec<Node>,
+}
+
+impl BinaryTree {
+    pub fn new(len: usize) -> Self {
+        BinaryTree {
+            nodes: vec![Node::default(); len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn set_children(
+        &mut self,
+        id: usize,
+        left: Option<usize>,
+        right: Option<usize>,
+    ) -> Result<(), BinaryTreeError> {
+        let len = self.len();
+        let children = left.into_iter().chain(right);
+        if let Some(x) = std::iter::once(id)
+            .chain(children.clone())
+            .find(|&x| x >= len)
+        {
+            return Err(BinaryTreeError::NodeOutOfRange(x));
+        }
+        if children.clone().any(|c| c == id) {
+            return Err(BinaryTreeError::SelfChild(id));
+        }
+        if let (Some(l), Some(r)) = (left, right) {
+            if l == r {
+                return Err(BinaryTreeError::DuplicateChild(l));
+            }
+        }
+        if let Some(c) = children
+            .clone()
+            .find(|&c| matches!(self.nodes[c].parent, Some(p) if p != id))
+        {
+            return Err(BinaryTreeError::MultipleParents(c));
+        }
+
+        for c in self.nodes[id].left.into_iter().chain(self.nodes[id].right) {
+            self.nodes[c].parent = None;
+        }
+        self.nodes[id].left = left;
+        self.nodes[id].right = right;
+        for c in children {
+            self.nodes[c].parent = Some(id);
+        }
+        Ok(())
+    }
+
+    pub fn parent(&self, id: usize) -> Option<usize> {
+        self.nodes[id].parent
+    }
+
+    pub fn left(&self, id: usize) -> Option<usize> {
+        self.nodes[id].left
+    }
+
+    pub fn right(&self, id: usize) -> Option<usize> {
+        self.nodes[id].right
+    }
+
+    pub fn root(&self) -> Option<usize> {
+        self.nodes.iter().position(|n| n.parent.is_none())
+    }
+
+    pub fn sibling(&self, id: usize) -> Option<usize> {
+        let parent = &self.nodes[self.parent(id)?];
+        if parent.left == Some(id) {
+            parent.right
+        } else {
+            parent.left
+        }
+    }
+
+    pub fn degree(&self, id: usize) -> usize {
+        let node = &self.nodes[id];
+        node.left.iter().chain(node.right.iter()).count()
+    }
+
+    pub fn node_type(&self, id: usize) -> NodeType {
+        match (self.parent(id), self.degree(id)) {
+            (None, _) => NodeType::Root,
+            (_, 0) => NodeType::Leaf,
+            _ => NodeType::InternalNode,
+        }
+    }
+
+    pub fn depths(&self) -> Vec<usize> {
+        let mut depths = vec![0; self.len()];
+        for id in self.traverse(Order::Preorder) {
+            if let Some(p) = self.parent(id) {
+                depths[id] = depths[p] + 1;
+            }
+        }
+        depths
+    }
+
+    /// Height of every node: the number of edges on the longest path down to a leaf.
+    pub fn heights(&self) -> Vec<usize> {
+        let mut heights = vec![0; self.len()];
+        for id in self.traverse(Order::Postorder) {
+            let node = &self.nodes[id];
+            heights[id] = node
+                .left
+                .iter()
+                .chain(node.right.iter())
+                .map(|&c| heights[c] + 1)
+                .max()
+                .unwrap_or(0);
+        }
+        heights
+    }
+
+    /// Walks the tree recursively. Deep trees may overflow the call stack;
+    /// see `traverse` for the iterative version.
+    pub fn traverse_recursive(&self, order: Order) -> Vec<usize> {
+        fn walk(tree: &BinaryTree, id: Option<usize>, order: Order, result: &mut Vec<usize>) {
+            let id = match id {
+                Some(id) => id,
+                None => return,
+            };
+            let node = &tree.nodes[id];
+            if order == Order::Preorder {
+                result.push(id);
+            }
+            walk(tree, node.left, order, result);
+            if order == Order::Inorder {
+                result.push(id);
+            }
+            walk(tree, node.right, order, result);
+            if order == Order::Postorder {
+                result.push(id);
+            }
+        }
+
+        let mut result = Vec::with_capacity(self.len());
+        walk(self, self.root(), order, &mut result);
+        result
+    }
+
+    /// Walks the tree with an explicit stack.
+    pub fn traverse(&self, order: Order) -> Vec<usize> {
+        let mut result = Vec::with_capacity(self.len());
+        // (node, whether its children are already on the stack)
+        let mut stack: Vec<(usize, bool)> = self.root().map(|r| (r, false)).into_iter().collect();
+
+        while let Some((id, expanded)) = stack.pop() {
+            if expanded {
+                result.push(id);
+                continue;
+            }
+
+            // pushed in reverse, so the left subtree is walked first
+            let node = &self.nodes[id];
+            if order == Order::Postorder {
+                stack.push((id, true));
+            }
+            if let Some(right) = node.right {
+                stack.push((right, false));
+            }
+            if order == Order::Inorder {
+                stack.push((id, true));
+            }
+            if let Some(left) = node.left {
+                stack.push((left, false));
+            }
+            if order == Order::Preorder {
+                stack.push((id, true));
+            }
+        }
+        result
+    }
+
+    pub fn level_order(&self) -> Vec<usize> {
+        let mut result = Vec::with_capacity(self.len());
+        let mut queue = VecQueue::new(self.len());
+        if let Some(root) = self.root() {
+            queue.enqueue(root);
+        }
+
+        while !queue.is_empty() {
+            let id = queue.dequeue();
+            result.push(id);
+            let node = &self.nodes[id];
+            for c in node.left.into_iter().chain(node.right) {
+                queue.enqueue(c);
+            }
+        }
+        result
+    }
+
+    /// Rebuilds the tree over nodes `0..n` from its preorder and inorder sequences.
+    pub fn from_preorder_inorder(
+        preorder: &[usize],
+        inorder: &[usize],
+    ) -> Result<Self, BinaryTreeError> {
+        let len = preorder.len();
+        if inorder.len() != len {
+            return Err(BinaryTreeError::InvalidTraversal);
+        }
+
+        // both must be permutations of 0..len before any links are made
+        positions(preorder)?;
+        let position = positions(inorder)?;
+
+        let mut tree = BinaryTree::new(len);
+        let mut stack: Vec<usize> = preorder.first().copied().into_iter().collect();
+        for &x in preorder.iter().skip(1) {
+            let pos = position[x];
+            let top = *stack.last().unwrap();
+
+            if pos < position[top] {
+                tree.nodes[top].left = Some(x);
+                tree.nodes[x].parent = Some(top);
+            } else {
+                // x is the right child of the last ancestor that precedes it in inorder
+                let mut parent = top;
+                while let Some(&y) = stack.last() {
+                    if position[y] > pos {
+                        break;
+                    }
+                    parent = stack.pop().unwrap();
+                }
+                tree.nodes[parent].right = Some(x);
+                tree.nodes[x].parent = Some(parent);
+            }
+            stack.push(x);
+        }
+
+        if tree.traverse(Order::Preorder) != preorder || tree.traverse(Order::Inorder) != inorder {
+            return Err(BinaryTreeError::InvalidTraversal);
+        }
+        Ok(tree)
+    }
+}
+
+/// Index of every node in `seq`, which must hold each of `0..seq.len()` once.
+fn positions(seq: &[usize]) -> Result<Vec<usize>, BinaryTreeError> {
+    let mut position = vec![None; seq.len()];
+    for (i, &x) in seq.iter().enumerate() {
+        match position.get_mut(x) {
+            Some(p @ None) => *p = Some(i),
+            Some(Some(_)) => return Err(BinaryTreeError::InvalidTraversal),
+            None => return Err(BinaryTreeError::NodeOutOfRange(x)),
+        }
+    }
+    Ok(position.into_iter().map(Option::unwrap).collect())
+}
+
+fn parse_id(s: &str) -> Result<Option<usize>, Box<dyn Error>> {
+    let id: isize = s.parse()?;
+    Ok(if id < 0 { None } else { Some(id as usize) })
+}
+
+fn read_binary_tree(reader: &mut impl Read) -> Result<BinaryTree, Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let len: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut tree = BinaryTree::new(len);
+
+    for line in lines.take(len) {
+        let line = line?;
+        let mut values = line.split_whitespace();
+        let id = parse_id(values.next().unwrap())?.ok_or("negative node id")?;
+        let left = parse_id(values.next().unwrap())?;
+        let right = parse_id(values.next().unwrap())?;
+        tree.set_children(id, left, right)?;
+    }
+
+    Ok(tree)
+}
+
+pub fn input_binary_tree(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let tree = read_binary_tree(reader)?;
+    let depths = tree.depths();
+    let heights = tree.heights();
+    let to_string = |x: Option<usize>| x.map_or(-1, |x| x as isize);
+
+    for id in 0..tree.len() {
+        writeln!(
+            writer,
+            "node {}: parent = {}, sibling = {}, degree = {}, depth = {}, height = {}, {}",
+            id,
+            to_string(tree.parent(id)),
+            to_string(tree.sibling(id)),
+            tree.degree(id),
+            depths[id],
+            heights[id],
+            tree.node_type(id)
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn input_tree_walk(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let tree = read_binary_tree(reader)?;
+    let walks = [
+        ("Preorder", Order::Preorder),
+        ("Inorder", Order::Inorder),
+        ("Postorder", Order::Postorder),
+    ];
+
+    for (name, order) in walks.iter() {
+        writeln!(writer, "{}", name)?;
+        writeln!(writer, " {}", tree.traverse(*order).into_iter().join(" "))?;
+    }
+
+    Ok(())
+}
+
+/// Reads the preorder and inorder walks of a tree over nodes `1..=n` and writes its postorder.
+pub fn input_reconstruction(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let _len: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut read_walk = || -> Result<Vec<usize>, Box<dyn Error>> {
+        let line = lines.next().ok_or("missing line")??;
+        line.split_whitespace()
+            .map(|x| match x.parse::<usize>()? {
+                0 => Err("node ids start at 1".into()),
+                x => Ok(x - 1),
+            })
+            .collect()
+    };
+    let preorder = read_walk()?;
+    let inorder = read_walk()?;
+
+    let tree = BinaryTree::from_preorder_inorder(&preorder, &inorder)?;
+    writeln!(
+        writer,
+        "{}",
+        tree.traverse(Order::Postorder)
+            .into_iter()
+            .map(|x| x + 1)
+            .join(" ")
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> String {
+        [
+            "9", "0 1 4", "1 2 3", "2 -1 -1", "3 -1 -1", "4 5 8", "5 6 7", "6 -1 -1", "7 -1 -1",
+            "8 -1 -1",
+        ]
+        .join("\n")
+    }
+
+    #[test]
+    fn test1() {
+        let input = sample();
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_binary_tree(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "node 0: parent = -1, sibling = -1, degree = 2, depth = 0, height = 3, root",
+                "node 1: parent = 0, sibling = 4, degree = 2, depth = 1, height = 1, internal node",
+                "node 2: parent = 1, sibling = 3, degree = 0, depth = 2, height = 0, leaf",
+                "node 3: parent = 1, sibling = 2, degree = 0, depth = 2, height = 0, leaf",
+                "node 4: parent = 0, sibling = 1, degree = 2, depth = 1, height = 2, internal node",
+                "node 5: parent = 4, sibling = 8, degree = 2, depth = 2, height = 1, internal node",
+                "node 6: parent = 5, sibling = 7, degree = 0, depth = 3, height = 0, leaf",
+                "node 7: parent = 5, sibling = 6, degree = 0, depth = 3, height = 0, leaf",
+                "node 8: parent = 4, sibling = 5, degree = 0, depth = 2, height = 0, leaf",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test2() {
+        let input = sample();
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_tree_walk(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "Preorder",
+                " 0 1 2 3 4 5 6 7 8",
+                "Inorder",
+                " 2 1 3 0 6 5 7 4 8",
+                "Postorder",
+                " 2 3 1 6 7 5 8 4 0",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test3() {
+        let run = |input: &[&str]| {
+            let input = input.join("\n");
+            let mut output: Vec<u8> = vec![];
+            let result = input_reconstruction(&mut input.as_bytes(), &mut output);
+            assert!(result.is_ok());
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(run(&["5", "1 2 3 4 5", "3 2 4 1 5"]), "3 4 2 5 1\n");
+        assert_eq!(run(&["4", "1 2 3 4", "1 2 3 4"]), "4 3 2 1\n");
+    }
+
+    #[test]
+    fn test_traversal() {
+        let tree = read_binary_tree(&mut sample().as_bytes()).unwrap();
+
+        for &order in &[Order::Preorder, Order::Inorder, Order::Postorder] {
+            assert_eq!(tree.traverse(order), tree.traverse_recursive(order));
+        }
+        assert_eq!(tree.level_order(), vec![0, 1, 4, 2, 3, 5, 8, 6, 7]);
+
+        let rebuilt = BinaryTree::from_preorder_inorder(
+            &tree.traverse(Order::Preorder),
+            &tree.traverse(Order::Inorder),
+        )
+        .unwrap();
+        assert_eq!(rebuilt, tree);
+
+        assert_eq!(
+            BinaryTree::from_preorder_inorder(&[0, 1, 2], &[2, 0, 1]),
+            Err(BinaryTreeError::InvalidTraversal)
+        );
+        assert_eq!(
+            BinaryTree::from_preorder_inorder(&[0, 1], &[1, 1]),
+            Err(BinaryTreeError::InvalidTraversal)
+        );
+        // a repeated id in preorder would link a node to itself
+        assert_eq!(
+            BinaryTree::from_preorder_inorder(&[0, 1, 1], &[1, 0, 2]),
+            Err(BinaryTreeError::InvalidTraversal)
+        );
+        assert_eq!(
+            BinaryTree::from_preorder_inorder(&[0, 5, 1], &[1, 0, 2]),
+            Err(BinaryTreeError::NodeOutOfRange(5))
+        );
+        assert_eq!(
+            BinaryTree::from_preorder_inorder(&[0, 1], &[1, 0, 2]),
+            Err(BinaryTreeError::InvalidTraversal)
+        );
+    }
+
+    #[test]
+    fn test_set_children() {
+        let mut tree = BinaryTree::new(3);
+        assert_eq!(
+            tree.set_children(0, Some(0), None),
+            Err(BinaryTreeError::SelfChild(0))
+        );
+        assert_eq!(
+            tree.set_children(0, Some(1), Some(1)),
+            Err(BinaryTreeError::DuplicateChild(1))
+        );
+        assert_eq!(
+            tree.set_children(0, Some(1), Some(3)),
+            Err(BinaryTreeError::NodeOutOfRange(3))
+        );
+        tree.set_children(0, Some(1), Some(2)).unwrap();
+        assert_eq!(
+            tree.set_children(1, None, Some(2)),
+            Err(BinaryTreeError::MultipleParents(2))
+        );
+        assert_eq!(tree.root(), Some(0));
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        let len = 200_000;
+        let preorder: Vec<usize> = (0..len).collect();
+        let inorder: Vec<usize> = (0..len).rev().collect();
+
+        let tree = BinaryTree::from_preorder_inorder(&preorder, &inorder).unwrap();
+
+        assert_eq!(tree.heights()[0], len - 1);
+        assert_eq!(tree.traverse(Order::Postorder), inorder);
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    error::Error,
+    io::{prelude::*, BufReader},
+};
+
+pub trait PriorityQueue<T> {
+    fn insert(&mut self, x: T);
+    /// Removes and returns the largest item.
+    fn extract(&mut self) -> Option<T>;
+    fn peek(&self) -> Option<&T>;
+    fn is_empty(&self) -> bool;
+    fn len(&self) -> usize;
+}
+
+/// A binary max-heap stored level by level in a `Vec`; the children of `i` are
+/// `2i + 1` and `2i + 2`.
+#[derive(Debug, Clone)]
+pub struct VecHeap<T> {
+    items: Vec<T>,
+}
+
+impl<T: Ord> VecHeap<T> {
+    pub fn new(capacity: usize) -> Self {
+        VecHeap {
+            items: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.items[parent] >= self.items[i] {
+                break;
+            }
+            self.items.swap(parent, i);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        let len = self.items.len();
+        loop {
+            let mut largest = i;
+            for child in (2 * i + 1..len).take(2) {
+                if self.items[child] > self.items[largest] {
+                    largest = child;
+                }
+            }
+            if largest == i {
+                break;
+            }
+            self.items.swap(i, largest);
+            i = largest;
+        }
+    }
+
+    /// The items in heap order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for VecHeap<T> {
+    /// Builds the heap in O(n) by sifting down every inner node.
+    fn from(items: Vec<T>) -> Self {
+        let mut heap = VecHeap { items };
+        for i in (0..heap.items.len() / 2).rev() {
+            heap.sift_down(i);
+        }
+        heap
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for VecHeap<T> {
+    fn insert(&mut self, x: T) {
+        self.items.push(x);
+        self.sift_up(self.items.len() - 1);
+    }
+
+    fn extract(&mut self) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+        let result = self.items.swap_remove(0);
+        self.sift_down(0);
+        Some(result)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.items.first()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+/// ALDS1_9_C: runs `insert k` and `extract` until `end`, printing every extracted key.
+pub fn input_priority_queue(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut heap = VecHeap::new(0);
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut line = line.split_whitespace();
+        match (line.next(), line.next()) {
+            (Some("insert"), Some(x)) => heap.insert(x.parse::<isize>()?),
+            (Some("extract"), None) => {
+                let x = heap.extract().ok_or("extract from an empty queue")?;
+                writeln!(writer, "{}", x)?;
+            }
+            (Some("end"), None) => break,
+            (command, _) => return Err(format!("unknown command: {:?}", command).into()),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let input = [
+            "insert 8",
+            "insert 2",
+            "extract",
+            "insert 10",
+            "extract",
+            "insert 11",
+            "extract",
+            "extract",
+            "end",
+        ]
+        .join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_priority_queue(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ["8", "10", "11", "2", ""].join("\n")
+        );
+    }
+
+    #[test]
+    fn test_build_heap() {
+        // ALDS1_9_B
+        let heap = VecHeap::from(vec![4, 1, 3, 2, 16, 9, 10, 14, 8, 7]);
+        assert_eq!(heap.as_slice(), &[16, 14, 10, 8, 7, 9, 3, 2, 4, 1]);
+
+        let mut heap = heap;
+        let mut sorted = vec![];
+        while let Some(x) = heap.extract() {
+            sorted.push(x);
+        }
+        assert_eq!(sorted, vec![16, 14, 10, 9, 8, 7, 4, 3, 2, 1]);
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+    }
+}
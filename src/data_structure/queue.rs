@@ -1,4 +1,4 @@
-use std::{error::Error, io::prelude::*};
+use std::{collections::VecDeque, error::Error, io::prelude::*, mem::MaybeUninit};
 
 pub trait Queue<T> {
     fn enqueue(&mut self, x: T);
@@ -8,42 +8,47 @@ pub trait Queue<T> {
     fn len(&self) -> usize;
 }
 
+/// A growable queue. It reports full once it holds `capacity` items but still
+/// accepts more.
 pub struct VecQueue<T> {
-    items: Vec<T>,
+    items: VecDeque<T>,
+    capacity: usize,
 }
 
 impl<T> VecQueue<T> {
     pub fn new(capacity: usize) -> Self {
         VecQueue {
-            items: Vec::with_capacity(capacity),
+            items: VecDeque::with_capacity(capacity),
+            capacity,
         }
     }
 }
 
 impl<T> Queue<T> for VecQueue<T> {
     fn enqueue(&mut self, x: T) {
-        self.items.push(x);
+        self.items.push_back(x);
     }
     fn dequeue(&mut self) -> T {
-        self.items.remove(0)
+        self.items.pop_front().expect("dequeue from an empty queue")
     }
     fn is_empty(&self) -> bool {
-        self.items.len() == 0
+        self.items.is_empty()
     }
     fn is_full(&self) -> bool {
-        self.items.len() == self.len()
+        self.items.len() >= self.capacity
     }
     fn len(&self) -> usize {
-        self.items.capacity()
+        self.items.len()
     }
 }
 
 const ARRAY_QUEUE_SIZE: usize = 256;
-use std::mem::MaybeUninit;
+
+/// A fixed-size ring buffer.
 pub struct ArrayQueue<T> {
     items: [MaybeUninit<T>; ARRAY_QUEUE_SIZE],
     head: usize,
-    tail: usize,
+    len: usize,
 }
 
 impl<T> ArrayQueue<T> {
@@ -51,51 +56,68 @@ impl<T> ArrayQueue<T> {
         Self {
             items: unsafe { MaybeUninit::uninit().assume_init() },
             head: 0,
-            tail: 0,
+            len: 0,
         }
     }
 }
 
+impl<T> Default for ArrayQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> Queue<T> for ArrayQueue<T> {
     fn enqueue(&mut self, x: T) {
-        let item = self.items.get_mut(self.tail).unwrap();
-        self.tail += 1;
-
-        unsafe {
-            *item.as_mut_ptr() = x;
-        }
+        assert!(!self.is_full(), "enqueue to a full queue");
+        let tail = (self.head + self.len) % ARRAY_QUEUE_SIZE;
+        self.items[tail] = MaybeUninit::new(x);
+        self.len += 1;
     }
     fn dequeue(&mut self) -> T {
-        let item = self.items.get_mut(self.head).unwrap();
-        self.head += 1;
-
-        let r: MaybeUninit<T> = std::mem::replace(item, MaybeUninit::uninit());
-        unsafe { r.assume_init() }
+        assert!(!self.is_empty(), "dequeue from an empty queue");
+        let item = std::mem::replace(&mut self.items[self.head], MaybeUninit::uninit());
+        self.head = (self.head + 1) % ARRAY_QUEUE_SIZE;
+        self.len -= 1;
+        unsafe { item.assume_init() }
     }
     fn is_empty(&self) -> bool {
-        self.tail == self.head
+        self.len == 0
     }
     fn is_full(&self) -> bool {
-        self.tail >= ARRAY_QUEUE_SIZE
+        self.len == ARRAY_QUEUE_SIZE
     }
     fn len(&self) -> usize {
-        self.tail - self.head
+        self.len
+    }
+}
+
+impl<T> Drop for ArrayQueue<T> {
+    fn drop(&mut self) {
+        while !self.is_empty() {
+            self.dequeue();
+        }
     }
 }
 
-#[derive(Eq, PartialEq, Clone, Default, Hash)]
+#[derive(Debug, Eq, PartialEq, Clone, Default, Hash)]
 pub struct Process {
-    name: String,
-    time: usize,
-    elapsed: usize,
+    pub name: String,
+    /// Remaining CPU time.
+    pub time: usize,
+    /// Time at which the process finished.
+    pub elapsed: usize,
+    pub arrival: usize,
+    /// Smaller values are scheduled first.
+    pub priority: usize,
 }
 
 impl Process {
-    fn new(name: String, time: usize) -> Self {
+    pub fn new(name: String, time: usize) -> Self {
         Self {
             name,
             time,
-            elapsed: 0,
+            ..Default::default()
         }
     }
 }
@@ -159,9 +181,13 @@ mod test {
         q.enqueue(100);
 
         assert!(q.is_full());
+        assert_eq!(q.len(), 3);
+        q.enqueue(1000);
+        assert_eq!(q.len(), 4);
         assert_eq!(1, q.dequeue());
         assert_eq!(10, q.dequeue());
         assert_eq!(100, q.dequeue());
+        assert_eq!(1000, q.dequeue());
         assert!(q.is_empty());
     }
 
@@ -179,6 +205,29 @@ mod test {
         assert!(q.is_empty());
     }
 
+    #[test]
+    fn test_array_queue_wraps_around() {
+        let mut q = ArrayQueue::new();
+        for round in 0..3 {
+            for i in 0..ARRAY_QUEUE_SIZE {
+                q.enqueue(round * 1000 + i);
+            }
+            assert!(q.is_full());
+            assert_eq!(q.len(), ARRAY_QUEUE_SIZE);
+            for i in 0..ARRAY_QUEUE_SIZE - 1 {
+                assert_eq!(q.dequeue(), round * 1000 + i);
+            }
+            q.dequeue();
+        }
+        assert!(q.is_empty());
+
+        // the remaining strings are dropped with the queue
+        let mut q = ArrayQueue::new();
+        q.enqueue(String::from("a"));
+        q.enqueue(String::from("b"));
+        assert_eq!(q.dequeue(), "a");
+    }
+
     #[test]
     fn test1() {
         let input = ["5 100", "p1 150", "p2 80", "p3 200", "p4 350", "p5 20"].join("\n");
@@ -0,0 +1,306 @@
+use itertools::Itertools;
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt::Display,
+    io::{prelude::*, BufReader},
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum NodeType {
+    Root,
+    InternalNode,
+    Leaf,
+}
+
+impl Display for NodeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            NodeType::Root => "root",
+            NodeType::InternalNode => "internal node",
+            NodeType::Leaf => "leaf",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RootedTreeError {
+    NodeOutOfRange(usize),
+    MultipleParents(usize),
+    /// A node listed as its own child.
+    SelfChild(usize),
+    /// A child listed more than once.
+    DuplicateChild(usize),
+}
+
+impl Display for RootedTreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RootedTreeError: {:?}", self)
+    }
+}
+
+impl Error for RootedTreeError {}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+struct Node {
+    parent: Option<usize>,
+    /// The leftmost child.
+    left: Option<usize>,
+    /// The next sibling to the right.
+    right: Option<usize>,
+}
+
+/// A rooted tree with nodes `0..n` in left-child, right-sibling representation.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RootedTree {
+    nodes: Vec<Node>,
+}
+
+impl RootedTree {
+    pub fn new(len: usize) -> Self {
+        RootedTree {
+            nodes: vec![Node::default(); len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Replaces the children of `id` with `children`, from left to right.
+    pub fn set_children(&mut self, id: usize, children: &[usize]) -> Result<(), RootedTreeError> {
+        let len = self.len();
+        if let Some(&x) = std::iter::once(&id).chain(children).find(|&&x| x >= len) {
+            return Err(RootedTreeError::NodeOutOfRange(x));
+        }
+        if children.contains(&id) {
+            return Err(RootedTreeError::SelfChild(id));
+        }
+        let mut seen = HashSet::with_capacity(children.len());
+        if let Some(&c) = children.iter().find(|&&c| !seen.insert(c)) {
+            return Err(RootedTreeError::DuplicateChild(c));
+        }
+        if let Some(&c) = children
+            .iter()
+            .find(|&&c| matches!(self.nodes[c].parent, Some(p) if p != id))
+        {
+            return Err(RootedTreeError::MultipleParents(c));
+        }
+
+        for c in self.children(id) {
+            self.nodes[c].parent = None;
+            self.nodes[c].right = None;
+        }
+        self.nodes[id].left = children.first().copied();
+        for (i, &c) in children.iter().enumerate() {
+            self.nodes[c].parent = Some(id);
+            self.nodes[c].right = children.get(i + 1).copied();
+        }
+        Ok(())
+    }
+
+    pub fn parent(&self, id: usize) -> Option<usize> {
+        self.nodes[id].parent
+    }
+
+    pub fn children(&self, id: usize) -> Vec<usize> {
+        let mut result = vec![];
+        let mut child = self.nodes[id].left;
+        while let Some(c) = child {
+            result.push(c);
+            child = self.nodes[c].right;
+        }
+        result
+    }
+
+    pub fn root(&self) -> Option<usize> {
+        self.nodes.iter().position(|n| n.parent.is_none())
+    }
+
+    pub fn node_type(&self, id: usize) -> NodeType {
+        let node = &self.nodes[id];
+        match (node.parent, node.left) {
+            (None, _) => NodeType::Root,
+            (_, Some(_)) => NodeType::InternalNode,
+            (_, None) => NodeType::Leaf,
+        }
+    }
+
+    /// Nodes in preorder, walked with an explicit stack.
+    pub fn preorder(&self) -> Vec<usize> {
+        let mut result = Vec::with_capacity(self.len());
+        let mut stack: Vec<usize> = self.root().into_iter().collect();
+        while let Some(id) = stack.pop() {
+            result.push(id);
+            let node = &self.nodes[id];
+            // the root's siblings are not part of the tree
+            if let (Some(_), Some(right)) = (node.parent, node.right) {
+                stack.push(right);
+            }
+            if let Some(left) = node.left {
+                stack.push(left);
+            }
+        }
+        result
+    }
+
+    /// Nodes in postorder, walked with an explicit stack.
+    pub fn postorder(&self) -> Vec<usize> {
+        let mut result = Vec::with_capacity(self.len());
+        let mut stack: Vec<(usize, bool)> = self.root().map(|r| (r, false)).into_iter().collect();
+        while let Some((id, expanded)) = stack.pop() {
+            if expanded {
+                result.push(id);
+                continue;
+            }
+            stack.push((id, true));
+            for c in self.children(id).into_iter().rev() {
+                stack.push((c, false));
+            }
+        }
+        result
+    }
+
+    /// Depth of every node; unreachable nodes get 0.
+    pub fn depths(&self) -> Vec<usize> {
+        let mut depths = vec![0; self.len()];
+        for id in self.preorder() {
+            if let Some(p) = self.nodes[id].parent {
+                depths[id] = depths[p] + 1;
+            }
+        }
+        depths
+    }
+}
+
+pub fn input_rooted_tree(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let len: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut tree = RootedTree::new(len);
+
+    for line in lines.take(len) {
+        let values = line?
+            .split_whitespace()
+            .map(|x| x.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let k = values[1];
+        tree.set_children(values[0], &values[2..2 + k])?;
+    }
+
+    let depths = tree.depths();
+    for (id, depth) in depths.into_iter().enumerate() {
+        writeln!(
+            writer,
+            "node {}: parent = {}, depth = {}, {}, [{}]",
+            id,
+            tree.parent(id).map_or(-1, |p| p as isize),
+            depth,
+            tree.node_type(id),
+            tree.children(id).into_iter().join(", ")
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let input = [
+            "13",
+            "0 3 1 4 10",
+            "1 2 2 3",
+            "2 0",
+            "3 0",
+            "4 3 5 6 7",
+            "5 0",
+            "6 0",
+            "7 2 8 9",
+            "8 0",
+            "9 0",
+            "10 2 11 12",
+            "11 0",
+            "12 0",
+        ]
+        .join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_rooted_tree(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "node 0: parent = -1, depth = 0, root, [1, 4, 10]",
+                "node 1: parent = 0, depth = 1, internal node, [2, 3]",
+                "node 2: parent = 1, depth = 2, leaf, []",
+                "node 3: parent = 1, depth = 2, leaf, []",
+                "node 4: parent = 0, depth = 1, internal node, [5, 6, 7]",
+                "node 5: parent = 4, depth = 2, leaf, []",
+                "node 6: parent = 4, depth = 2, leaf, []",
+                "node 7: parent = 4, depth = 2, internal node, [8, 9]",
+                "node 8: parent = 7, depth = 3, leaf, []",
+                "node 9: parent = 7, depth = 3, leaf, []",
+                "node 10: parent = 0, depth = 1, internal node, [11, 12]",
+                "node 11: parent = 10, depth = 2, leaf, []",
+                "node 12: parent = 10, depth = 2, leaf, []",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_traversal() {
+        let mut tree = RootedTree::new(6);
+        tree.set_children(3, &[0, 5]).unwrap();
+        tree.set_children(0, &[1, 2]).unwrap();
+        tree.set_children(5, &[4]).unwrap();
+
+        assert_eq!(tree.root(), Some(3));
+        assert_eq!(tree.preorder(), vec![3, 0, 1, 2, 5, 4]);
+        assert_eq!(tree.postorder(), vec![1, 2, 0, 4, 5, 3]);
+        assert_eq!(
+            tree.set_children(1, &[6]),
+            Err(RootedTreeError::NodeOutOfRange(6))
+        );
+        assert_eq!(
+            tree.set_children(1, &[4]),
+            Err(RootedTreeError::MultipleParents(4))
+        );
+        assert_eq!(
+            tree.set_children(1, &[1]),
+            Err(RootedTreeError::SelfChild(1))
+        );
+        assert_eq!(
+            tree.set_children(2, &[4, 1, 4]),
+            Err(RootedTreeError::DuplicateChild(4))
+        );
+        assert_eq!(tree.children(2), vec![]);
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        let len = 200_000;
+        let mut tree = RootedTree::new(len);
+        for i in 0..len - 1 {
+            tree.set_children(i, &[i + 1]).unwrap();
+        }
+
+        assert_eq!(tree.depths()[len - 1], len - 1);
+        assert_eq!(tree.postorder()[0], len - 1);
+        assert_eq!(tree.node_type(len - 1), NodeType::Leaf);
+    }
+}
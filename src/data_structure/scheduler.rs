@@ -0,0 +1,448 @@
+use super::queue::{Process, Queue, VecQueue};
+use std::{error::Error, fmt::Display};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Policy {
+    /// First come, first served.
+    Fcfs,
+    /// Shortest job first (non-preemptive).
+    Sjf,
+    /// Shortest remaining time first (preemptive SJF).
+    Srtf,
+    /// Smallest `priority` first (non-preemptive).
+    Priority,
+    /// Smallest `priority` first, preempted by arrivals with a smaller value.
+    PreemptivePriority,
+    /// Round robin with the given quantum.
+    RoundRobin(usize),
+    /// Multilevel feedback queue with one quantum per level.
+    ///
+    /// New processes enter level 0 and drop one level every time they use up
+    /// their whole quantum. The last level is round robin. A slice is never cut
+    /// short: a process arriving at a higher level waits for the running slice to
+    /// end and is then scheduled ahead of the preempted process.
+    Mlfq(Vec<usize>),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SchedulerError {
+    ZeroQuantum,
+    NoLevels,
+}
+
+impl Display for SchedulerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SchedulerError: {:?}", self)
+    }
+}
+
+impl Error for SchedulerError {}
+
+/// A span of the Gantt timeline during which `name` held the CPU.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Slice {
+    /// Index of the process in the input, since names need not be unique.
+    pub process: usize,
+    pub name: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Report {
+    pub name: String,
+    pub arrival: usize,
+    pub burst: usize,
+    pub finish: usize,
+    pub turnaround: usize,
+    pub waiting: usize,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Schedule {
+    /// One report per process, in input order.
+    pub reports: Vec<Report>,
+    pub timeline: Vec<Slice>,
+}
+
+impl Schedule {
+    /// The slices of the `process`-th input process.
+    pub fn timeline_of(&self, process: usize) -> impl Iterator<Item = &Slice> + '_ {
+        self.timeline.iter().filter(move |s| s.process == process)
+    }
+
+    /// `None` for a schedule without processes.
+    pub fn average_turnaround(&self) -> Option<f64> {
+        self.average(|r| r.turnaround)
+    }
+
+    /// `None` for a schedule without processes.
+    pub fn average_waiting(&self) -> Option<f64> {
+        self.average(|r| r.waiting)
+    }
+
+    fn average(&self, f: impl Fn(&Report) -> usize) -> Option<f64> {
+        if self.reports.is_empty() {
+            return None;
+        }
+        Some(self.reports.iter().map(f).sum::<usize>() as f64 / self.reports.len() as f64)
+    }
+}
+
+struct Simulation<'a> {
+    processes: &'a [Process],
+    /// Process indices sorted by arrival time.
+    arrivals: Vec<usize>,
+    next_arrival: usize,
+    remaining: Vec<usize>,
+    finish: Vec<usize>,
+    timeline: Vec<Slice>,
+    now: usize,
+    done: usize,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(processes: &'a [Process]) -> Self {
+        let mut arrivals: Vec<usize> = (0..processes.len()).collect();
+        arrivals.sort_by_key(|&i| processes[i].arrival);
+
+        Self {
+            processes,
+            arrivals,
+            next_arrival: 0,
+            remaining: processes.iter().map(|p| p.time).collect(),
+            finish: vec![0; processes.len()],
+            timeline: vec![],
+            now: 0,
+            done: 0,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.done == self.processes.len()
+    }
+
+    /// Returns the processes that have arrived by `now` and were not admitted yet.
+    fn admit(&mut self) -> Vec<usize> {
+        let mut admitted = vec![];
+        while let Some(&i) = self.arrivals.get(self.next_arrival) {
+            if self.processes[i].arrival > self.now {
+                break;
+            }
+            admitted.push(i);
+            self.next_arrival += 1;
+        }
+        admitted
+    }
+
+    fn next_arrival_time(&self) -> Option<usize> {
+        self.arrivals
+            .get(self.next_arrival)
+            .map(|&i| self.processes[i].arrival)
+    }
+
+    /// Jumps over an idle period to the next arrival.
+    fn idle(&mut self) {
+        if let Some(t) = self.next_arrival_time() {
+            self.now = self.now.max(t);
+        }
+    }
+
+    /// Runs process `i` for `time` units and returns whether it has finished.
+    fn run(&mut self, i: usize, time: usize) -> bool {
+        match self.timeline.last_mut() {
+            _ if time == 0 => {}
+            Some(last) if last.end == self.now && last.process == i => last.end += time,
+            _ => self.timeline.push(Slice {
+                process: i,
+                name: self.processes[i].name.clone(),
+                start: self.now,
+                end: self.now + time,
+            }),
+        }
+
+        self.now += time;
+        self.remaining[i] -= time;
+        if self.remaining[i] == 0 {
+            self.finish[i] = self.now;
+            self.done += 1;
+            return true;
+        }
+        false
+    }
+
+    fn into_schedule(self) -> Schedule {
+        let reports = self
+            .processes
+            .iter()
+            .zip(self.finish)
+            .map(|(p, finish)| {
+                let turnaround = finish - p.arrival;
+                Report {
+                    name: p.name.clone(),
+                    arrival: p.arrival,
+                    burst: p.time,
+                    finish,
+                    turnaround,
+                    waiting: turnaround - p.time,
+                }
+            })
+            .collect();
+
+        Schedule {
+            reports,
+            timeline: self.timeline,
+        }
+    }
+}
+
+/// Always runs the ready process with the smallest key, ties broken by arrival.
+fn run_by_key<K: Ord>(
+    processes: &[Process],
+    preemptive: bool,
+    key: impl Fn(&Process, usize) -> K,
+) -> Schedule {
+    let mut sim = Simulation::new(processes);
+    let mut ready: Vec<usize> = vec![];
+
+    while !sim.is_finished() {
+        ready.extend(sim.admit());
+        if ready.is_empty() {
+            sim.idle();
+            continue;
+        }
+
+        let (pos, &i) = ready
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &i)| {
+                (
+                    key(&processes[i], sim.remaining[i]),
+                    processes[i].arrival,
+                    i,
+                )
+            })
+            .unwrap();
+
+        let time = match sim.next_arrival_time() {
+            Some(t) if preemptive => sim.remaining[i].min(t - sim.now),
+            _ => sim.remaining[i],
+        };
+        if sim.run(i, time) {
+            ready.remove(pos);
+        }
+    }
+
+    sim.into_schedule()
+}
+
+fn run_feedback(processes: &[Process], quanta: &[usize]) -> Schedule {
+    let mut sim = Simulation::new(processes);
+    let mut levels: Vec<VecQueue<usize>> = quanta
+        .iter()
+        .map(|_| VecQueue::new(processes.len()))
+        .collect();
+
+    while !sim.is_finished() {
+        for i in sim.admit() {
+            levels[0].enqueue(i);
+        }
+
+        let level = match levels.iter().position(|q| !q.is_empty()) {
+            Some(level) => level,
+            None => {
+                sim.idle();
+                continue;
+            }
+        };
+
+        let i = levels[level].dequeue();
+        let time = sim.remaining[i].min(quanta[level]);
+        let finished = sim.run(i, time);
+
+        // processes that arrived during the slice are queued ahead of the preempted one
+        for j in sim.admit() {
+            levels[0].enqueue(j);
+        }
+        if !finished {
+            let next = (level + 1).min(levels.len() - 1);
+            levels[next].enqueue(i);
+        }
+    }
+
+    sim.into_schedule()
+}
+
+pub fn simulate(processes: &[Process], policy: &Policy) -> Result<Schedule, SchedulerError> {
+    let schedule = match policy {
+        Policy::Fcfs => run_by_key(processes, false, |_, _| ()),
+        Policy::Sjf => run_by_key(processes, false, |p, _| p.time),
+        Policy::Srtf => run_by_key(processes, true, |_, remaining| remaining),
+        Policy::Priority => run_by_key(processes, false, |p, _| p.priority),
+        Policy::PreemptivePriority => run_by_key(processes, true, |p, _| p.priority),
+        Policy::RoundRobin(quantum) => {
+            if *quantum == 0 {
+                return Err(SchedulerError::ZeroQuantum);
+            }
+            run_feedback(processes, &[*quantum])
+        }
+        Policy::Mlfq(quanta) => {
+            if quanta.is_empty() {
+                return Err(SchedulerError::NoLevels);
+            }
+            if quanta.contains(&0) {
+                return Err(SchedulerError::ZeroQuantum);
+            }
+            run_feedback(processes, quanta)
+        }
+    };
+
+    Ok(schedule)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn process(name: &str, time: usize, arrival: usize, priority: usize) -> Process {
+        Process {
+            name: name.into(),
+            time,
+            arrival,
+            priority,
+            ..Default::default()
+        }
+    }
+
+    fn finish_times(schedule: &Schedule) -> Vec<usize> {
+        schedule.reports.iter().map(|r| r.finish).collect()
+    }
+
+    fn gantt(schedule: &Schedule) -> Vec<(&str, usize, usize)> {
+        schedule
+            .timeline
+            .iter()
+            .map(|s| (s.name.as_str(), s.start, s.end))
+            .collect()
+    }
+
+    fn sample() -> Vec<Process> {
+        vec![
+            process("p1", 8, 0, 3),
+            process("p2", 4, 1, 1),
+            process("p3", 9, 2, 4),
+            process("p4", 5, 3, 2),
+        ]
+    }
+
+    #[test]
+    fn test_fcfs_sjf() {
+        let schedule = simulate(&sample(), &Policy::Fcfs).unwrap();
+        assert_eq!(finish_times(&schedule), vec![8, 12, 21, 26]);
+        assert_eq!(
+            schedule
+                .reports
+                .iter()
+                .map(|r| r.waiting)
+                .collect::<Vec<_>>(),
+            vec![0, 7, 10, 18]
+        );
+
+        let schedule = simulate(&sample(), &Policy::Sjf).unwrap();
+        assert_eq!(finish_times(&schedule), vec![8, 12, 26, 17]);
+
+        let schedule = simulate(&sample(), &Policy::Priority).unwrap();
+        assert_eq!(finish_times(&schedule), vec![8, 12, 26, 17]);
+    }
+
+    #[test]
+    fn test_srtf() {
+        let schedule = simulate(&sample(), &Policy::Srtf).unwrap();
+        assert_eq!(finish_times(&schedule), vec![17, 5, 26, 10]);
+        assert_eq!(schedule.average_waiting(), Some(6.5));
+        assert_eq!(
+            gantt(&schedule),
+            vec![
+                ("p1", 0, 1),
+                ("p2", 1, 5),
+                ("p4", 5, 10),
+                ("p1", 10, 17),
+                ("p3", 17, 26)
+            ]
+        );
+        assert_eq!(
+            schedule
+                .timeline_of(0)
+                .map(|s| s.end - s.start)
+                .sum::<usize>(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_idle() {
+        let processes = vec![process("a", 2, 0, 0), process("b", 3, 5, 0)];
+        let schedule = simulate(&processes, &Policy::Srtf).unwrap();
+
+        assert_eq!(gantt(&schedule), vec![("a", 0, 2), ("b", 5, 8)]);
+        assert_eq!(schedule.reports[1].waiting, 0);
+
+        // processes sharing a name keep separate slices
+        let processes = vec![process("a", 2, 0, 0), process("a", 3, 0, 0)];
+        let schedule = simulate(&processes, &Policy::Fcfs).unwrap();
+        assert_eq!(gantt(&schedule), vec![("a", 0, 2), ("a", 2, 5)]);
+        assert_eq!(schedule.timeline_of(1).count(), 1);
+
+        let schedule = simulate(&[], &Policy::Fcfs).unwrap();
+        assert_eq!(schedule.average_turnaround(), None);
+        assert_eq!(schedule.average_waiting(), None);
+    }
+
+    #[test]
+    fn test_round_robin() {
+        // same scenario as `queue::compute`
+        let processes = vec![
+            process("p1", 150, 0, 0),
+            process("p2", 80, 0, 0),
+            process("p3", 200, 0, 0),
+            process("p4", 350, 0, 0),
+            process("p5", 20, 0, 0),
+        ];
+        let schedule = simulate(&processes, &Policy::RoundRobin(100)).unwrap();
+        assert_eq!(finish_times(&schedule), vec![450, 180, 550, 800, 400]);
+
+        let schedule = simulate(&sample(), &Policy::RoundRobin(4)).unwrap();
+        assert_eq!(finish_times(&schedule), vec![20, 8, 26, 25]);
+
+        assert_eq!(
+            simulate(&sample(), &Policy::RoundRobin(0)),
+            Err(SchedulerError::ZeroQuantum)
+        );
+    }
+
+    #[test]
+    fn test_mlfq() {
+        let schedule = simulate(&sample(), &Policy::Mlfq(vec![2, 4, 8])).unwrap();
+        assert_eq!(
+            gantt(&schedule),
+            vec![
+                ("p1", 0, 2),
+                ("p2", 2, 4),
+                ("p3", 4, 6),
+                ("p4", 6, 8),
+                ("p1", 8, 12),
+                ("p2", 12, 14),
+                ("p3", 14, 18),
+                ("p4", 18, 21),
+                ("p1", 21, 23),
+                ("p3", 23, 26)
+            ]
+        );
+        assert_eq!(finish_times(&schedule), vec![23, 14, 26, 21]);
+
+        assert_eq!(
+            simulate(&sample(), &Policy::Mlfq(vec![])),
+            Err(SchedulerError::NoLevels)
+        );
+    }
+}
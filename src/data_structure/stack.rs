@@ -25,19 +25,220 @@ pub enum Op {
     Plus,
     Minus,
     Mul,
+    Div,
+    Mod,
+    Pow,
+    /// Unary minus. Written as `~` in postfix notation.
+    Neg,
 }
 
 impl Op {
     pub fn from(op: &char) -> Option<Self> {
-        match op {
-            &'+' => Some(Op::Plus),
-            &'-' => Some(Op::Minus),
-            &'*' => Some(Op::Mul),
+        match *op {
+            '+' => Some(Op::Plus),
+            '-' => Some(Op::Minus),
+            '*' => Some(Op::Mul),
+            '/' => Some(Op::Div),
+            '%' => Some(Op::Mod),
+            '^' => Some(Op::Pow),
+            '~' => Some(Op::Neg),
             _ => None,
         }
     }
+
+    pub fn arity(&self) -> usize {
+        match self {
+            Op::Neg => 1,
+            _ => 2,
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Op::Plus | Op::Minus => 1,
+            Op::Mul | Op::Div | Op::Mod => 2,
+            Op::Neg => 3,
+            Op::Pow => 4,
+        }
+    }
+
+    fn is_right_assoc(&self) -> bool {
+        matches!(self, Op::Pow | Op::Neg)
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Op::Plus => '+',
+            Op::Minus => '-',
+            Op::Mul => '*',
+            Op::Div => '/',
+            Op::Mod => '%',
+            Op::Pow => '^',
+            Op::Neg => '~',
+        }
+    }
+
+    /// Applies a binary operator. `Op::Neg` ignores `lhs`.
+    pub fn apply(&self, lhs: isize, rhs: isize) -> Result<isize, ArithmeticError> {
+        match self {
+            Op::Plus => lhs.checked_add(rhs).ok_or(ArithmeticError::Overflow),
+            Op::Minus => lhs.checked_sub(rhs).ok_or(ArithmeticError::Overflow),
+            Op::Mul => lhs.checked_mul(rhs).ok_or(ArithmeticError::Overflow),
+            Op::Div | Op::Mod if rhs == 0 => Err(ArithmeticError::DivisionByZero),
+            Op::Div => lhs.checked_div(rhs).ok_or(ArithmeticError::Overflow),
+            Op::Mod => lhs.checked_rem(rhs).ok_or(ArithmeticError::Overflow),
+            Op::Pow if rhs < 0 => Err(ArithmeticError::NegativeExponent),
+            Op::Pow => {
+                if rhs > u32::MAX as isize {
+                    return Err(ArithmeticError::Overflow);
+                }
+                lhs.checked_pow(rhs as u32).ok_or(ArithmeticError::Overflow)
+            }
+            Op::Neg => rhs.checked_neg().ok_or(ArithmeticError::Overflow),
+        }
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl Display for Ident {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ident::Int(x) => write!(f, "{}", x),
+            Ident::Op(op) => write!(f, "{}", op),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum ArithmeticError {
+    Overflow,
+    DivisionByZero,
+    NegativeExponent,
+}
+
+impl Display for ArithmeticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArithmeticError::Overflow => write!(f, "ArithmeticError: overflow"),
+            ArithmeticError::DivisionByZero => write!(f, "ArithmeticError: division by zero"),
+            ArithmeticError::NegativeExponent => write!(f, "ArithmeticError: negative exponent"),
+        }
+    }
+}
+impl Error for ArithmeticError {}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum ParseError {
+    UnknownToken { position: usize, token: String },
+    MissingOperand { position: usize },
+    MissingOperator { position: usize },
+    MismatchedParenthesis { position: usize },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnknownToken { position, token } => {
+                write!(f, "ParseError: unknown token {:?} at {}", token, position)
+            }
+            ParseError::MissingOperand { position } => {
+                write!(f, "ParseError: missing operand at {}", position)
+            }
+            ParseError::MissingOperator { position } => {
+                write!(f, "ParseError: missing operator at {}", position)
+            }
+            ParseError::MismatchedParenthesis { position } => {
+                write!(f, "ParseError: mismatched parenthesis at {}", position)
+            }
+        }
+    }
+}
+impl Error for ParseError {}
+
+/// Errors of postfix evaluation. `position` is the index of the offending token, or its
+/// byte offset in the source for errors from `eval_infix`.
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum EvalError {
+    EmptyExpression,
+    UnknownToken { position: usize, token: String },
+    StackUnderflow { position: usize },
+    StackOverflow { position: usize },
+    TooManyOperands { remaining: usize },
+    Overflow { position: usize },
+    DivisionByZero { position: usize },
+    NegativeExponent { position: usize },
 }
 
+impl EvalError {
+    fn from_arithmetic(e: ArithmeticError, position: usize) -> Self {
+        match e {
+            ArithmeticError::Overflow => EvalError::Overflow { position },
+            ArithmeticError::DivisionByZero => EvalError::DivisionByZero { position },
+            ArithmeticError::NegativeExponent => EvalError::NegativeExponent { position },
+        }
+    }
+
+    fn map_position(self, f: impl FnOnce(usize) -> usize) -> Self {
+        match self {
+            EvalError::UnknownToken { position, token } => EvalError::UnknownToken {
+                position: f(position),
+                token,
+            },
+            EvalError::StackUnderflow { position } => EvalError::StackUnderflow {
+                position: f(position),
+            },
+            EvalError::StackOverflow { position } => EvalError::StackOverflow {
+                position: f(position),
+            },
+            EvalError::Overflow { position } => EvalError::Overflow {
+                position: f(position),
+            },
+            EvalError::DivisionByZero { position } => EvalError::DivisionByZero {
+                position: f(position),
+            },
+            EvalError::NegativeExponent { position } => EvalError::NegativeExponent {
+                position: f(position),
+            },
+            e @ EvalError::EmptyExpression | e @ EvalError::TooManyOperands { .. } => e,
+        }
+    }
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalError::EmptyExpression => write!(f, "EvalError: empty expression"),
+            EvalError::UnknownToken { position, token } => {
+                write!(f, "EvalError: unknown token {:?} at {}", token, position)
+            }
+            EvalError::StackUnderflow { position } => {
+                write!(f, "EvalError: too few operands at {}", position)
+            }
+            EvalError::StackOverflow { position } => {
+                write!(f, "EvalError: stack is full at {}", position)
+            }
+            EvalError::TooManyOperands { remaining } => {
+                write!(f, "EvalError: {} operands left on the stack", remaining)
+            }
+            EvalError::Overflow { position } => {
+                write!(f, "EvalError: overflow at {}", position)
+            }
+            EvalError::DivisionByZero { position } => {
+                write!(f, "EvalError: division by zero at {}", position)
+            }
+            EvalError::NegativeExponent { position } => {
+                write!(f, "EvalError: negative exponent at {}", position)
+            }
+        }
+    }
+}
+impl Error for EvalError {}
+
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
 pub enum PushError {
     IsFull,
@@ -45,7 +246,7 @@ pub enum PushError {
 
 impl Display for PushError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
-        write!(f, "PushError: {}", self.to_string())
+        write!(f, "PushError: {:?}", self)
     }
 }
 impl Error for PushError {}
@@ -57,7 +258,7 @@ pub enum PopError {
 
 impl Display for PopError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
-        write!(f, "PopError: {}", self.to_string())
+        write!(f, "PopError: {:?}", self)
     }
 }
 impl Error for PopError {}
@@ -68,6 +269,7 @@ pub trait Stack<T> {
     fn is_empty(&self) -> bool;
     fn is_full(&self) -> bool;
     fn capacity(&self) -> usize;
+    fn peek(&self) -> Option<&T>;
 }
 
 impl<T> Stack<T> for VecStack<T> {
@@ -95,6 +297,10 @@ impl<T> Stack<T> for VecStack<T> {
     fn capacity(&self) -> usize {
         self.items.capacity()
     }
+
+    fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
 }
 
 pub struct VecStack<T> {
@@ -160,44 +366,274 @@ impl<T> Stack<T> for ArrayStack<T> {
     fn capacity(&self) -> usize {
         ARRAY_STACK_SIZE
     }
+    fn peek(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        unsafe { Some(&*self.items[self.tail - 1].as_ptr()) }
+    }
 }
 
-fn compute(items: &[Ident]) -> Result<isize, Box<dyn Error>> {
+pub fn compute(items: &[Ident]) -> Result<isize, EvalError> {
+    if items.is_empty() {
+        return Err(EvalError::EmptyExpression);
+    }
+
     let mut stack = ArrayStack::new();
-    for item in items {
-        match item {
-            Ident::Int(item) => {
-                if let Err(e) = stack.push(*item) {
-                    return Err(Box::new(e));
-                }
+    for (position, item) in items.iter().enumerate() {
+        let x = match item {
+            Ident::Int(item) => *item,
+            Ident::Op(op) if op.arity() == 1 => {
+                let op1 = stack
+                    .pop()
+                    .map_err(|_| EvalError::StackUnderflow { position })?;
+                op.apply(0, op1)
+                    .map_err(|e| EvalError::from_arithmetic(e, position))?
             }
             Ident::Op(op) => match (stack.pop(), stack.pop()) {
-                (Ok(op1), Ok(op2)) => match op {
-                    Op::Plus => stack.push(op2 + op1),
-                    Op::Minus => stack.push(op2 - op1),
-                    Op::Mul => stack.push(op2 * op1),
-                }?,
-                (_, Err(e)) => return Err(Box::new(e)),
-                (Err(e), _) => return Err(Box::new(e)),
+                (Ok(op1), Ok(op2)) => op
+                    .apply(op2, op1)
+                    .map_err(|e| EvalError::from_arithmetic(e, position))?,
+                _ => return Err(EvalError::StackUnderflow { position }),
+            },
+        };
+        stack
+            .push(x)
+            .map_err(|_| EvalError::StackOverflow { position })?;
+    }
+
+    let result = stack.pop().map_err(|_| EvalError::EmptyExpression)?;
+    if !stack.is_empty() {
+        let mut remaining = 1;
+        while stack.pop().is_ok() {
+            remaining += 1;
+        }
+        return Err(EvalError::TooManyOperands { remaining });
+    }
+
+    Ok(result)
+}
+
+/// Splits a postfix expression on whitespace into `Ident`s.
+pub fn parse_postfix(s: &str) -> Result<Vec<Ident>, EvalError> {
+    s.split_whitespace()
+        .enumerate()
+        .map(|(position, token)| {
+            Ident::from(token).ok_or_else(|| EvalError::UnknownToken {
+                position,
+                token: token.into(),
+            })
+        })
+        .collect()
+}
+
+enum Token {
+    Ident(Ident),
+    LParen,
+    RParen,
+}
+
+fn tokenize_infix(s: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let mut tokens: Vec<(usize, Token)> = vec![];
+    let mut chars = s.char_indices().peekable();
+    // true while the next token has to be an operand (number, `(` or unary minus)
+    let mut expect_operand = true;
+
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '0'..='9' => {
+                let mut end = position + c.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let literal = &s[position..end];
+                let x = literal.parse().map_err(|_| ParseError::UnknownToken {
+                    position,
+                    token: literal.into(),
+                })?;
+                Token::Ident(Ident::Int(x))
+            }
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '-' if expect_operand => Token::Ident(Ident::Op(Op::Neg)),
+            c => match Op::from(&c) {
+                Some(Op::Neg) | None => {
+                    return Err(ParseError::UnknownToken {
+                        position,
+                        token: c.to_string(),
+                    })
+                }
+                Some(op) => Token::Ident(Ident::Op(op)),
+            },
+        };
+
+        expect_operand = match (&token, expect_operand) {
+            (Token::Ident(Ident::Int(_)), true) | (Token::RParen, false) => false,
+            (Token::LParen, true) | (Token::Ident(Ident::Op(Op::Neg)), true) => true,
+            (Token::Ident(Ident::Op(_)), false) => true,
+            (Token::Ident(Ident::Op(_)), true) | (Token::RParen, true) => {
+                return Err(ParseError::MissingOperand { position })
+            }
+            (Token::Ident(Ident::Int(_)), false) | (Token::LParen, false) => {
+                return Err(ParseError::MissingOperator { position })
+            }
+        };
+        tokens.push((position, token));
+    }
+
+    if expect_operand {
+        return Err(ParseError::MissingOperand { position: s.len() });
+    }
+
+    Ok(tokens)
+}
+
+/// Converts an infix expression into postfix `Ident`s with the shunting-yard algorithm.
+///
+/// `^` is right associative and binds tighter than unary minus, so `-2^2` is `-(2^2)`.
+pub fn parse_infix(s: &str) -> Result<Vec<Ident>, ParseError> {
+    let items = parse_infix_with_offsets(s)?;
+    Ok(items.into_iter().map(|(_, item)| item).collect())
+}
+
+/// `parse_infix` keeping the byte offset in `s` of the token each `Ident` came from.
+fn parse_infix_with_offsets(s: &str) -> Result<Vec<(usize, Ident)>, ParseError> {
+    enum Pending {
+        Op(usize, Op),
+        LParen(usize),
+    }
+
+    let tokens = tokenize_infix(s)?;
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut ops: VecStack<Pending> = VecStack::new(tokens.len());
+
+    for (position, token) in tokens {
+        match token {
+            Token::Ident(Ident::Int(x)) => output.push((position, Ident::Int(x))),
+            Token::Ident(Ident::Op(op)) => {
+                // a prefix operator has no left operand, so nothing can be reduced yet
+                if op.arity() == 2 {
+                    while let Some(&Pending::Op(top_position, top)) = ops.peek() {
+                        if top.precedence() > op.precedence()
+                            || (top.precedence() == op.precedence() && !op.is_right_assoc())
+                        {
+                            output.push((top_position, Ident::Op(top)));
+                            ops.pop().unwrap();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                ops.push(Pending::Op(position, op)).unwrap();
+            }
+            Token::LParen => ops.push(Pending::LParen(position)).unwrap(),
+            Token::RParen => loop {
+                match ops.pop() {
+                    Ok(Pending::Op(position, op)) => output.push((position, Ident::Op(op))),
+                    Ok(Pending::LParen(_)) => break,
+                    Err(_) => return Err(ParseError::MismatchedParenthesis { position }),
+                }
             },
         }
     }
 
-    Ok(stack.pop()?)
+    while let Ok(pending) = ops.pop() {
+        match pending {
+            Pending::Op(position, op) => output.push((position, Ident::Op(op))),
+            Pending::LParen(position) => {
+                return Err(ParseError::MismatchedParenthesis { position })
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Evaluates an infix expression. Positions in both parse and evaluation errors are byte
+/// offsets into `s`.
+pub fn eval_infix(s: &str) -> Result<isize, Box<dyn Error>> {
+    let (offsets, items): (Vec<_>, Vec<_>) = parse_infix_with_offsets(s)?.into_iter().unzip();
+    let result = compute(&items).map_err(|e| e.map_position(|i| offsets[i]))?;
+    Ok(result)
+}
+
+/// Converts postfix `Ident`s back into infix notation, adding only the parentheses
+/// needed to reproduce the same evaluation order.
+pub fn to_infix(items: &[Ident]) -> Result<String, EvalError> {
+    const ATOM: u8 = u8::MAX;
+
+    fn wrap(s: String, needs_paren: bool) -> String {
+        if needs_paren {
+            format!("({})", s)
+        } else {
+            s
+        }
+    }
+
+    if items.is_empty() {
+        return Err(EvalError::EmptyExpression);
+    }
+
+    // every token pushes at most one entry, so the pushes below cannot fail
+    let mut stack: VecStack<(String, u8)> = VecStack::new(items.len());
+    for (position, item) in items.iter().enumerate() {
+        let underflow = |_| EvalError::StackUnderflow { position };
+        match item {
+            Ident::Int(x) if *x < 0 => stack.push((x.to_string(), Op::Neg.precedence())).unwrap(),
+            Ident::Int(x) => stack.push((x.to_string(), ATOM)).unwrap(),
+            Ident::Op(op) if op.arity() == 1 => {
+                let (operand, prec) = stack.pop().map_err(underflow)?;
+                let operand = wrap(operand, prec < op.precedence());
+                stack
+                    .push((format!("-{}", operand), op.precedence()))
+                    .unwrap();
+            }
+            Ident::Op(op) => {
+                let (rhs, rhs_prec) = stack.pop().map_err(underflow)?;
+                let (lhs, lhs_prec) = stack.pop().map_err(underflow)?;
+                let p = op.precedence();
+                let lhs = wrap(lhs, lhs_prec < p || (lhs_prec == p && op.is_right_assoc()));
+                let rhs = wrap(rhs, rhs_prec < p || (rhs_prec == p && !op.is_right_assoc()));
+                stack.push((format!("{} {} {}", lhs, op, rhs), p)).unwrap();
+            }
+        }
+    }
+
+    let (result, _) = stack.pop().map_err(|_| EvalError::EmptyExpression)?;
+    if !stack.is_empty() {
+        let mut remaining = 1;
+        while stack.pop().is_ok() {
+            remaining += 1;
+        }
+        return Err(EvalError::TooManyOperands { remaining });
+    }
+
+    Ok(result)
 }
 
 pub fn input_stack(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
     let mut buf = String::new();
     reader.read_to_string(&mut buf)?;
-    let items: Vec<Ident> = buf
-        .split_whitespace()
-        .map(|s| Ident::from(s).unwrap())
-        .collect();
+    let items = parse_postfix(&buf)?;
     let result = compute(&items)?;
     write!(writer, "{}", result)?;
     Ok(())
 }
 
+pub fn input_infix(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let mut buf = String::new();
+    reader.read_to_string(&mut buf)?;
+    let result = eval_infix(buf.trim_end())?;
+    write!(writer, "{}", result)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -256,4 +692,153 @@ mod test {
         assert!(result.is_ok());
         assert_eq!(String::from_utf8(output).unwrap(), "-3".to_string());
     }
+
+    fn eval(s: &str) -> Result<isize, Box<dyn Error>> {
+        eval_infix(s)
+    }
+
+    #[test]
+    fn test_parse_infix() {
+        let postfix = |s: &str| {
+            parse_infix(s)
+                .unwrap()
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        assert_eq!(postfix("(1 + 2) * (3 - 4)"), "1 2 + 3 4 - *");
+        assert_eq!(postfix("1+2*3"), "1 2 3 * +");
+        assert_eq!(postfix("2 ^ 3 ^ 2"), "2 3 2 ^ ^");
+        assert_eq!(postfix("-2^2"), "2 2 ^ ~");
+        assert_eq!(postfix("10 - -3 * 2"), "10 3 ~ 2 * -");
+
+        assert_eq!(
+            parse_infix("(1 + 2"),
+            Err(ParseError::MismatchedParenthesis { position: 0 })
+        );
+        assert_eq!(
+            parse_infix("1 + 2)"),
+            Err(ParseError::MismatchedParenthesis { position: 5 })
+        );
+        assert_eq!(
+            parse_infix("1 +"),
+            Err(ParseError::MissingOperand { position: 3 })
+        );
+        assert_eq!(
+            parse_infix("1 2"),
+            Err(ParseError::MissingOperator { position: 2 })
+        );
+        assert_eq!(
+            parse_infix("1 & 2"),
+            Err(ParseError::UnknownToken {
+                position: 2,
+                token: "&".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_eval_infix() {
+        assert_eq!(eval("(1 + 2) * (3 - 4)").unwrap(), -3);
+        assert_eq!(eval("7 / 2 + 7 % 2").unwrap(), 4);
+        assert_eq!(eval("2 ^ 3 ^ 2").unwrap(), 512);
+        assert_eq!(eval("-2 ^ 2").unwrap(), -4);
+        assert_eq!(eval("(-2) ^ 2").unwrap(), 4);
+        assert_eq!(eval("--3").unwrap(), 3);
+
+        let err = |s: &str| *eval(s).unwrap_err().downcast::<EvalError>().unwrap();
+        assert_eq!(err("1 / 0"), EvalError::DivisionByZero { position: 2 });
+        assert_eq!(
+            err("1 % (2 - 2)"),
+            EvalError::DivisionByZero { position: 2 }
+        );
+        assert_eq!(
+            err("10 / (3 - 3)"),
+            EvalError::DivisionByZero { position: 3 }
+        );
+        assert_eq!(err("2 ^ -1"), EvalError::NegativeExponent { position: 2 });
+        assert_eq!(err("2 ^ 64"), EvalError::Overflow { position: 2 });
+        assert_eq!(
+            err(&format!("{} + 1", isize::MAX)),
+            EvalError::Overflow {
+                position: isize::MAX.to_string().len() + 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_to_infix() {
+        let round_trip = |s: &str| to_infix(&parse_infix(s).unwrap()).unwrap();
+
+        assert_eq!(round_trip("((1 + 2)) * (3 - 4)"), "(1 + 2) * (3 - 4)");
+        assert_eq!(round_trip("(1 * 2) + (3 * 4)"), "1 * 2 + 3 * 4");
+        assert_eq!(round_trip("(1 - 2) - 3"), "1 - 2 - 3");
+        assert_eq!(round_trip("1 - (2 - 3)"), "1 - (2 - 3)");
+        assert_eq!(round_trip("(2 ^ 3) ^ 2"), "(2 ^ 3) ^ 2");
+        assert_eq!(round_trip("2 ^ (3 ^ 2)"), "2 ^ 3 ^ 2");
+        assert_eq!(round_trip("-(1 + 2)"), "-(1 + 2)");
+        assert_eq!(round_trip("-(2 ^ 2)"), "-2 ^ 2");
+
+        assert_eq!(
+            to_infix(&[Ident::Int(-2), Ident::Int(2), Ident::Op(Op::Pow)]).unwrap(),
+            "(-2) ^ 2"
+        );
+        assert_eq!(
+            to_infix(&[Ident::Int(1), Ident::Op(Op::Plus)]),
+            Err(EvalError::StackUnderflow { position: 1 })
+        );
+    }
+
+    #[test]
+    fn test_eval_error() {
+        let eval = |s: &str| compute(&parse_postfix(s)?);
+
+        assert_eq!(eval("1 2 + 3 4 - *"), Ok(-3));
+        assert_eq!(eval(""), Err(EvalError::EmptyExpression));
+        assert_eq!(eval("1 +"), Err(EvalError::StackUnderflow { position: 1 }));
+        assert_eq!(eval("~"), Err(EvalError::StackUnderflow { position: 0 }));
+        assert_eq!(
+            eval("1 2"),
+            Err(EvalError::TooManyOperands { remaining: 2 })
+        );
+        assert_eq!(
+            eval("1 2 x +"),
+            Err(EvalError::UnknownToken {
+                position: 2,
+                token: "x".into()
+            })
+        );
+        assert_eq!(
+            eval("1 0 /"),
+            Err(EvalError::DivisionByZero { position: 2 })
+        );
+
+        let input = "1 2 3 +".to_string();
+        let mut output = vec![];
+        let result = input_stack(&mut input.as_bytes(), &mut output);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "EvalError: 2 operands left on the stack"
+        );
+    }
+
+    #[test]
+    fn test_input_infix() {
+        let input = "(1 + 2) * (3 - 4)\n".to_string();
+        let mut output = vec![];
+
+        let result = input_infix(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "-3".to_string());
+
+        let input = "  2 * (1 / 0)\n".to_string();
+        let result = input_infix(&mut input.as_bytes(), &mut vec![]);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "EvalError: division by zero at 9"
+        );
+    }
 }
@@ -0,0 +1,612 @@
+use crate::search::dictionary::Dictionary;
+use itertools::Itertools;
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::Display,
+    io::{prelude::*, BufReader},
+    ops::{Bound, RangeBounds},
+};
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    value: T,
+    priority: u64,
+    /// Number of nodes in this subtree.
+    size: usize,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> Node<T> {
+    fn new(value: T, priority: u64) -> Box<Self> {
+        Box::new(Node {
+            value,
+            priority,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    fn update(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+fn size<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |n| n.size)
+}
+
+/// Hangs `link` below the last node of `path` and every node below the one
+/// before it, fixing the sizes on the way up. `path` holds the nodes from the
+/// top down, each with whether the next one hangs on its left.
+fn reattach<T>(mut path: Vec<(Box<Node<T>>, bool)>, mut link: Link<T>) -> Link<T> {
+    while let Some((mut node, left)) = path.pop() {
+        if left {
+            node.left = link;
+        } else {
+            node.right = link;
+        }
+        node.update();
+        link = Some(node);
+    }
+    link
+}
+
+/// Splits into the values less than `value` and the rest.
+fn split<T: Ord>(link: Link<T>, value: &T) -> (Link<T>, Link<T>) {
+    // the nodes of each side along the search path, from the top down
+    let (mut lefts, mut rights) = (vec![], vec![]);
+    let mut cur = link;
+    while let Some(mut node) = cur {
+        if node.value < *value {
+            cur = node.right.take();
+            lefts.push((node, false));
+        } else {
+            cur = node.left.take();
+            rights.push((node, true));
+        }
+    }
+    (reattach(lefts, None), reattach(rights, None))
+}
+
+/// Joins two treaps where every value of `left` is less than every value of `right`.
+fn merge<T>(mut left: Link<T>, mut right: Link<T>) -> Link<T> {
+    let mut path = vec![];
+    loop {
+        match (left, right) {
+            (Some(mut l), Some(mut r)) => {
+                if l.priority > r.priority {
+                    left = l.right.take();
+                    right = Some(r);
+                    path.push((l, false));
+                } else {
+                    right = r.left.take();
+                    left = Some(l);
+                    path.push((r, true));
+                }
+            }
+            (l, r) => return reattach(path, l.or(r)),
+        }
+    }
+}
+
+/// A treap: a binary search tree on the values and a max-heap on the priorities.
+///
+/// `Dictionary::insert` draws priorities from an internal xorshift generator, so
+/// the tree is balanced in expectation whatever the insertion order. Explicit
+/// priorities from `insert_with_priority` carry no such guarantee: monotone ones
+/// make the tree a chain, so every operation walks the tree with a loop rather
+/// than recursion.
+#[derive(Debug)]
+pub struct Treap<T> {
+    root: Link<T>,
+    state: u64,
+}
+
+impl<T: Ord> Treap<T> {
+    pub fn new() -> Self {
+        Self::with_seed(0x2545_f491_4f6c_dd1d)
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Treap {
+            root: None,
+            // xorshift gets stuck at 0
+            state: seed.max(1),
+        }
+    }
+
+    fn next_priority(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Inserts `value` with an explicit priority. Does nothing if `value` is
+    /// already in the treap.
+    pub fn insert_with_priority(&mut self, value: T, priority: u64) {
+        if self.find(&value) {
+            return;
+        }
+
+        // descend until the new node outranks the subtree
+        let mut path = vec![];
+        let mut cur = self.root.take();
+        let subtree = loop {
+            match cur {
+                Some(mut node) if priority <= node.priority => {
+                    let left = value < node.value;
+                    cur = if left {
+                        node.left.take()
+                    } else {
+                        node.right.take()
+                    };
+                    path.push((node, left));
+                }
+                subtree => break subtree,
+            }
+        };
+
+        let (l, r) = split(subtree, &value);
+        let mut new = Node::new(value, priority);
+        new.left = l;
+        new.right = r;
+        new.update();
+        self.root = reattach(path, Some(new));
+    }
+
+    /// Removes `value` and returns it, or `None` if it is not in the treap.
+    pub fn delete(&mut self, value: &T) -> Option<T> {
+        let mut path = vec![];
+        let mut cur = self.root.take();
+        let mut removed = None;
+        while let Some(mut node) = cur {
+            let left = match value.cmp(&node.value) {
+                Ordering::Equal => {
+                    cur = merge(node.left.take(), node.right.take());
+                    removed = Some(node.value);
+                    break;
+                }
+                Ordering::Less => true,
+                Ordering::Greater => false,
+            };
+            cur = if left {
+                node.left.take()
+            } else {
+                node.right.take()
+            };
+            path.push((node, left));
+        }
+        self.root = reattach(path, cur);
+        removed
+    }
+
+    /// Moves the values greater than or equal to `value` into a new treap.
+    pub fn split_off(&mut self, value: &T) -> Self {
+        let (l, r) = split(self.root.take(), value);
+        self.root = l;
+        Treap {
+            root: r,
+            state: self.next_priority(),
+        }
+    }
+
+    /// Appends `other`, whose values must all be greater than those in `self`.
+    /// Gives `other` back untouched if the two overlap.
+    pub fn merge(&mut self, mut other: Self) -> Result<(), Self> {
+        if let (Some(max), Some(min)) = (self.max(), other.min()) {
+            if max >= min {
+                return Err(other);
+            }
+        }
+        self.root = merge(self.root.take(), other.root.take());
+        Ok(())
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.select(0)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.select(self.len().checked_sub(1)?)
+    }
+
+    /// Number of values less than `value`.
+    pub fn rank(&self, value: &T) -> usize {
+        let mut rank = 0;
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            if node.value < *value {
+                rank += size(&node.left) + 1;
+                cur = &node.right;
+            } else {
+                cur = &node.left;
+            }
+        }
+        rank
+    }
+
+    /// The `k`-th smallest value, counting from 0.
+    pub fn select(&self, mut k: usize) -> Option<&T> {
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            let left = size(&node.left);
+            match k.cmp(&left) {
+                Ordering::Less => cur = &node.left,
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => {
+                    k -= left + 1;
+                    cur = &node.right;
+                }
+            }
+        }
+        None
+    }
+
+    /// Values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.root, Bound::Unbounded)
+    }
+
+    /// Values within `range` in ascending order.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> impl Iterator<Item = &T> {
+        let start = match range.start_bound() {
+            Bound::Included(x) => self.rank(x),
+            Bound::Excluded(x) => self.rank(x) + self.find(x) as usize,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(x) => self.rank(x) + self.find(x) as usize,
+            Bound::Excluded(x) => self.rank(x),
+            Bound::Unbounded => self.len(),
+        };
+        Iter::new(&self.root, range.start_bound()).take(end.saturating_sub(start))
+    }
+
+    /// Values in preorder, walked with an explicit stack.
+    pub fn preorder(&self) -> Vec<&T> {
+        let mut result = Vec::with_capacity(self.len());
+        let mut stack: Vec<&Node<T>> = self.root.iter().map(|n| n.as_ref()).collect();
+        while let Some(node) = stack.pop() {
+            result.push(&node.value);
+            stack.extend(node.right.as_deref());
+            stack.extend(node.left.as_deref());
+        }
+        result
+    }
+}
+
+impl<T: Clone> Clone for Treap<T> {
+    /// Copies the nodes in reverse preorder, so both subtrees of a node are on top
+    /// of the stack by the time it is reached.
+    fn clone(&self) -> Self {
+        let mut built: Vec<Box<Node<T>>> = vec![];
+        let mut stack: Vec<&Node<T>> = self.root.iter().map(|n| n.as_ref()).collect();
+        let mut preorder = Vec::with_capacity(size(&self.root));
+        while let Some(node) = stack.pop() {
+            preorder.push(node);
+            stack.extend(node.right.as_deref());
+            stack.extend(node.left.as_deref());
+        }
+
+        for node in preorder.into_iter().rev() {
+            let mut new = Node::new(node.value.clone(), node.priority);
+            if node.left.is_some() {
+                new.left = built.pop();
+            }
+            if node.right.is_some() {
+                new.right = built.pop();
+            }
+            new.size = node.size;
+            built.push(new);
+        }
+
+        Treap {
+            root: built.pop(),
+            state: self.state,
+        }
+    }
+}
+
+impl<T> Drop for Treap<T> {
+    /// Frees the nodes one by one, as the default drop recurses as deep as the tree.
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+impl<T: Ord> Default for Treap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Dictionary<T> for Treap<T> {
+    /// Inserting a value already in the treap does nothing.
+    fn insert(&mut self, value: T) {
+        let priority = self.next_priority();
+        self.insert_with_priority(value, priority);
+    }
+
+    fn find(&self, value: &T) -> bool {
+        let mut cur = &self.root;
+        while let Some(node) = cur {
+            cur = match value.cmp(&node.value) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+}
+
+/// In-order iterator over a `Treap`.
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T: Ord> Iter<'a, T> {
+    /// Starts at the smallest value within `start`.
+    fn new(root: &'a Link<T>, start: Bound<&T>) -> Self {
+        let mut stack = vec![];
+        let mut cur = root.as_deref();
+        while let Some(node) = cur {
+            let in_range = match start {
+                Bound::Included(x) => node.value >= *x,
+                Bound::Excluded(x) => node.value > *x,
+                Bound::Unbounded => true,
+            };
+            if in_range {
+                stack.push(node);
+                cur = node.left.as_deref();
+            } else {
+                cur = node.right.as_deref();
+            }
+        }
+        Iter { stack }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let mut cur = node.right.as_deref();
+        while let Some(n) = cur {
+            self.stack.push(n);
+            cur = n.left.as_deref();
+        }
+        Some(&node.value)
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a Treap<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum Command<T> {
+    Insert(T, u64),
+    Find(T),
+    Delete(T),
+    Print,
+}
+
+impl Command<isize> {
+    fn parse(line: &str) -> Result<Self, Box<dyn Error>> {
+        let mut line = line.split_whitespace();
+        let command = match (line.next(), line.next(), line.next()) {
+            (Some("insert"), Some(x), Some(p)) => Command::Insert(x.parse()?, p.parse()?),
+            (Some("find"), Some(x), None) => Command::Find(x.parse()?),
+            (Some("delete"), Some(x), None) => Command::Delete(x.parse()?),
+            (Some("print"), None, None) => Command::Print,
+            (command, _, _) => return Err(format!("unknown command: {:?}", command).into()),
+        };
+        Ok(command)
+    }
+}
+
+/// Runs the commands; `find` prints `yes`/`no` and `print` prints the inorder and
+/// preorder walks, each value preceded by a space.
+pub fn compute<T: Ord + Clone + Display>(
+    commands: &[Command<T>],
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let mut treap = Treap::new();
+    for command in commands {
+        match command {
+            Command::Insert(x, p) => treap.insert_with_priority(x.clone(), *p),
+            Command::Find(x) => writeln!(writer, "{}", if treap.find(x) { "yes" } else { "no" })?,
+            Command::Delete(x) => {
+                treap.delete(x);
+            }
+            Command::Print => {
+                writeln!(writer, " {}", treap.iter().join(" "))?;
+                writeln!(writer, " {}", treap.preorder().into_iter().join(" "))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn input_treap(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let len: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut commands = Vec::with_capacity(len);
+    for line in lines.take(len) {
+        commands.push(Command::parse(&line?)?);
+    }
+
+    compute(&commands, writer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::XorShift;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test1() {
+        let input = [
+            "16",
+            "insert 35 99",
+            "insert 3 80",
+            "insert 1 53",
+            "insert 14 25",
+            "insert 80 76",
+            "insert 42 3",
+            "insert 86 47",
+            "insert 21 12",
+            "insert 7 10",
+            "insert 6 90",
+            "print",
+            "find 21",
+            "find 22",
+            "delete 35",
+            "delete 99",
+            "print",
+        ]
+        .join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_treap(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                " 1 3 6 7 14 21 35 42 80 86",
+                " 35 6 3 1 14 7 21 80 42 86",
+                "yes",
+                "no",
+                " 1 3 6 7 14 21 42 80 86",
+                " 6 3 1 80 14 7 21 42 86",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_split_merge() {
+        let mut treap = Treap::new();
+        for x in 0..100 {
+            treap.insert(x);
+        }
+
+        let mut upper = treap.split_off(&40);
+        assert_eq!(treap.len(), 40);
+        assert_eq!(upper.len(), 60);
+        assert_eq!(treap.max(), Some(&39));
+        assert_eq!(upper.min(), Some(&40));
+
+        upper.delete(&50);
+        let mut overlapping = Treap::new();
+        overlapping.insert(5);
+        let overlapping = treap.merge(overlapping).unwrap_err();
+        assert_eq!((treap.len(), overlapping.len()), (40, 1));
+        treap.merge(upper).unwrap();
+        assert_eq!(treap.len(), 99);
+        assert_eq!(treap.rank(&60), 59);
+        assert_eq!(treap.select(50), Some(&51));
+        assert!(treap.range(45..=52).eq([45, 46, 47, 48, 49, 51, 52].iter()));
+    }
+
+    #[test]
+    fn test_against_btree_set() {
+        let mut rng = XorShift::new(11);
+        let mut treap = Treap::new();
+        let mut expected = BTreeSet::new();
+        for _ in 0..2000 {
+            let x = rng.below(128);
+            if rng.below(3) == 0 {
+                assert_eq!(treap.delete(&x), expected.take(&x));
+            } else {
+                treap.insert(x);
+                expected.insert(x);
+            }
+            let y = x + rng.below(16);
+            assert_eq!(treap.len(), expected.len());
+            assert_eq!(treap.rank(&x), expected.range(..x).count());
+            assert_eq!(treap.select(x), expected.iter().nth(x));
+            assert!(treap.range(x..y).eq(expected.range(x..y)));
+            assert!(treap
+                .range((Bound::Excluded(x), Bound::Included(y)))
+                .eq(expected.range((Bound::Excluded(x), Bound::Included(y)))));
+        }
+        assert!(treap.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_sorted_insert() {
+        let mut treap = Treap::new();
+        for x in 0..100_000 {
+            treap.insert(x);
+        }
+        assert_eq!(treap.len(), 100_000);
+        assert_eq!(treap.select(12_345), Some(&12_345));
+
+        let copy = treap.clone();
+        assert!(copy.iter().eq(treap.iter()));
+        assert_eq!(copy.preorder(), treap.preorder());
+    }
+
+    #[test]
+    fn test_monotone_priorities() {
+        // every insert becomes the root, leaving a chain down the left
+        let len = 200_000;
+        let mut treap = Treap::new();
+        for x in 0..len {
+            treap.insert_with_priority(x, x as u64);
+        }
+        assert_eq!(treap.len(), len);
+        assert_eq!(treap.delete(&0), Some(0));
+        assert!(treap.find(&1));
+
+        let mut upper = treap.split_off(&(len / 2));
+        assert_eq!((treap.len(), upper.len()), (len / 2 - 1, len / 2));
+        assert_eq!(upper.delete(&(len / 2)), Some(len / 2));
+        treap.merge(upper).unwrap();
+        assert_eq!(treap.rank(&(len - 1)), len - 3);
+
+        let copy = treap.clone();
+        assert_eq!(copy.len(), len - 2);
+        assert_eq!(copy.preorder(), treap.preorder());
+
+        // equal priorities leave the shape to the insertion order, which a clone keeps
+        let mut treap = Treap::new();
+        for &x in &[3, 1, 4, 0, 5, 9, 2, 6] {
+            treap.insert_with_priority(x, 7);
+        }
+        let copy = treap.clone();
+        assert_eq!(copy.preorder(), treap.preorder());
+        assert_eq!(copy.select(5), Some(&5));
+    }
+}
@@ -0,0 +1,222 @@
+/// Disjoint sets over `0..n` with union by rank and path compression.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    /// Number of elements in the set, valid for roots only.
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+            count: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut cur = x;
+        while cur != root {
+            let next = self.parent[cur];
+            self.parent[cur] = root;
+            cur = next;
+        }
+        root
+    }
+
+    /// Merges the sets of `x` and `y`. Returns `false` if they were already one.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        if self.rank[x] < self.rank[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        if self.rank[x] == self.rank[y] {
+            self.rank[x] += 1;
+        }
+        self.count -= 1;
+        true
+    }
+
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Number of elements in the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+}
+
+/// Disjoint sets whose unions can be undone in reverse order.
+///
+/// There is no path compression, so `find` takes O(log n) and leaves the
+/// structure untouched.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+    /// For every successful union: the root attached below the other, and whether
+    /// the rank of the new root grew.
+    history: Vec<(usize, bool)>,
+}
+
+impl RollbackUnionFind {
+    pub fn new(len: usize) -> Self {
+        RollbackUnionFind {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+            count: len,
+            history: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn find(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        if self.rank[x] < self.rank[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        let grew = self.rank[x] == self.rank[y];
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        if grew {
+            self.rank[x] += 1;
+        }
+        self.count -= 1;
+        self.history.push((y, grew));
+        true
+    }
+
+    pub fn same(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    pub fn size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    /// A point to return to with `rollback`.
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes the last successful union. Returns `false` if there is none.
+    pub fn undo(&mut self) -> bool {
+        let (y, grew) = match self.history.pop() {
+            Some(last) => last,
+            None => return false,
+        };
+        let x = self.parent[y];
+        self.parent[y] = y;
+        self.size[x] -= self.size[y];
+        if grew {
+            self.rank[x] -= 1;
+        }
+        self.count += 1;
+        true
+    }
+
+    /// Undoes every union made since `snapshot`.
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut uf = UnionFind::new(6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+
+        assert!(uf.same(0, 3));
+        assert!(!uf.same(0, 4));
+        assert_eq!(uf.size(2), 4);
+        assert_eq!(uf.size(5), 1);
+        assert_eq!(uf.count(), 3);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut uf = RollbackUnionFind::new(5);
+        uf.union(0, 1);
+        let snapshot = uf.snapshot();
+        uf.union(2, 3);
+        uf.union(1, 3);
+        assert!(!uf.union(0, 2));
+        assert_eq!(uf.size(0), 4);
+        assert_eq!(uf.count(), 2);
+
+        assert!(uf.undo());
+        assert!(!uf.same(0, 2));
+        assert!(uf.same(2, 3));
+        uf.rollback(snapshot);
+        assert!(!uf.same(2, 3));
+        assert!(uf.same(0, 1));
+        assert_eq!(uf.size(0), 2);
+        assert_eq!(uf.count(), 4);
+
+        uf.rollback(0);
+        assert!(!uf.undo());
+        assert_eq!(uf, RollbackUnionFind::new(5));
+    }
+}
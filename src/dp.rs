@@ -0,0 +1,6 @@
+pub mod edit_distance;
+pub mod fibonacci;
+pub mod knapsack;
+pub mod lcs;
+pub mod lis;
+pub mod matrix_chain;
@@ -0,0 +1,256 @@
+use itertools::Itertools;
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{prelude::*, BufReader},
+};
+
+/// Levenshtein distance by plain recursion in exponential time.
+pub fn edit_distance_recursive<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    match (a.split_last(), b.split_last()) {
+        (None, _) => b.len(),
+        (_, None) => a.len(),
+        (Some((x, a_rest)), Some((y, b_rest))) => {
+            let substitute = edit_distance_recursive(a_rest, b_rest) + (x != y) as usize;
+            let delete = edit_distance_recursive(a_rest, b) + 1;
+            let insert = edit_distance_recursive(a, b_rest) + 1;
+            substitute.min(delete).min(insert)
+        }
+    }
+}
+
+/// Levenshtein distance in O(nm) time and O(m) space.
+pub fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        // the value of `row[j]` from the previous row
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (diagonal + (x != y) as usize)
+                .min(above + 1)
+                .min(row[j] + 1);
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Levenshtein distance if it is at most `band`, in O(n * band) time and
+/// O(band) space, looking only at cells within `band` of the diagonal.
+pub fn edit_distance_banded<T: PartialEq>(a: &[T], b: &[T], band: usize) -> Option<usize> {
+    const INF: usize = usize::MAX;
+    if a.len().max(b.len()) - a.len().min(b.len()) > band {
+        return None;
+    }
+    // no distance exceeds the longer length, and a wider band would only waste space
+    let band = band.min(a.len().max(b.len()));
+
+    // row[k] holds the cell for column j = i + k - band of the current row i
+    let width = 2 * band + 1;
+    let column = |i: usize, k: usize| (i + k).checked_sub(band).filter(|&j| j <= b.len());
+    let mut row: Vec<usize> = (0..width).map(|k| column(0, k).unwrap_or(INF)).collect();
+    for i in 1..=a.len() {
+        let mut next = vec![INF; width];
+        for k in 0..width {
+            let j = match column(i, k) {
+                Some(j) => j,
+                None => continue,
+            };
+            next[k] = if j == 0 {
+                i
+            } else {
+                let substitute = row[k].saturating_add((a[i - 1] != b[j - 1]) as usize);
+                let delete = row.get(k + 1).map_or(INF, |&d| d.saturating_add(1));
+                let insert = if k > 0 {
+                    next[k - 1].saturating_add(1)
+                } else {
+                    INF
+                };
+                substitute.min(delete).min(insert)
+            };
+        }
+        row = next;
+    }
+
+    // column b.len() of the last row
+    let distance = row[b.len() + band - a.len()];
+    Some(distance).filter(|&d| d <= band)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum EditOp {
+    Match,
+    Substitute,
+    /// Inserts the next element of the target.
+    Insert,
+    /// Deletes the next element of the source.
+    Delete,
+}
+
+/// A cheapest way to turn one sequence into another.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Alignment {
+    pub distance: usize,
+    pub ops: Vec<EditOp>,
+}
+
+impl Alignment {
+    /// Three lines showing the alignment of `a` to `b`, with `-` for gaps and
+    /// `|` between matching elements. Every column is as wide as its widest
+    /// element.
+    pub fn render<T: Display>(&self, a: &[T], b: &[T]) -> String {
+        let (mut a, mut b) = (a.iter(), b.iter());
+        let next = |side: &mut std::slice::Iter<'_, T>| side.next().unwrap().to_string();
+        let gap = || "-".to_string();
+        let mut lines = [vec![], vec![], vec![]];
+        for op in &self.ops {
+            let (x, mark, y) = match op {
+                EditOp::Match => (next(&mut a), "|", next(&mut b)),
+                EditOp::Substitute => (next(&mut a), " ", next(&mut b)),
+                EditOp::Insert => (gap(), " ", next(&mut b)),
+                EditOp::Delete => (next(&mut a), " ", gap()),
+            };
+            let width = x.chars().count().max(y.chars().count());
+            lines[0].push(format!("{:<1$}", x, width));
+            lines[1].push(format!("{:^1$}", mark, width));
+            lines[2].push(format!("{:<1$}", y, width));
+        }
+        lines.iter().map(|line| line.concat()).join("\n")
+    }
+}
+
+/// Levenshtein distance with the edits that achieve it, in O(nm).
+pub fn align<T: PartialEq>(a: &[T], b: &[T]) -> Alignment {
+    // table[i][j] is the distance between a[..i] and b[..j]
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (j, cell) in table[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for (i, x) in a.iter().enumerate() {
+        table[i + 1][0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            table[i + 1][j + 1] = (table[i][j] + (x != y) as usize)
+                .min(table[i][j + 1] + 1)
+                .min(table[i + 1][j] + 1);
+        }
+    }
+
+    let (mut i, mut j) = (a.len(), b.len());
+    let mut ops = vec![];
+    while i > 0 || j > 0 {
+        let op = if i > 0 && j > 0 && a[i - 1] == b[j - 1] && table[i][j] == table[i - 1][j - 1] {
+            EditOp::Match
+        } else if i > 0 && j > 0 && table[i][j] == table[i - 1][j - 1] + 1 {
+            EditOp::Substitute
+        } else if i > 0 && table[i][j] == table[i - 1][j] + 1 {
+            EditOp::Delete
+        } else {
+            EditOp::Insert
+        };
+        match op {
+            EditOp::Match | EditOp::Substitute => {
+                i -= 1;
+                j -= 1;
+            }
+            EditOp::Delete => i -= 1,
+            EditOp::Insert => j -= 1,
+        }
+        ops.push(op);
+    }
+    ops.reverse();
+    Alignment {
+        distance: table[a.len()][b.len()],
+        ops,
+    }
+}
+
+/// DPL_1_E: two lines; prints their edit distance.
+pub fn input_edit_distance(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let a = lines.next().ok_or("unexpected end of input")??;
+    let b = lines.next().ok_or("unexpected end of input")??;
+    writeln!(
+        writer,
+        "{}",
+        edit_distance(a.trim().as_bytes(), b.trim().as_bytes())
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        for (input, expected) in &[
+            (["acac", "acm"].join("\n"), "2\n"),
+            (["icpc", "icpc"].join("\n"), "0\n"),
+        ] {
+            let mut output: Vec<u8> = vec![];
+            let result = input_edit_distance(&mut input.as_bytes(), &mut output);
+            assert!(result.is_ok());
+            assert_eq!(String::from_utf8(output).unwrap(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_recursive() {
+        let words = [
+            "", "a", "kitten", "sitting", "acac", "acm", "abcab", "bacba",
+        ];
+        for a in &words {
+            for b in &words {
+                let expected = edit_distance_recursive(a.as_bytes(), b.as_bytes());
+                assert_eq!(edit_distance(a.as_bytes(), b.as_bytes()), expected);
+
+                let alignment = align(a.as_bytes(), b.as_bytes());
+                assert_eq!(alignment.distance, expected);
+                let cost = alignment
+                    .ops
+                    .iter()
+                    .filter(|&&op| op != EditOp::Match)
+                    .count();
+                assert_eq!(cost, expected);
+
+                for band in 0..8 {
+                    assert_eq!(
+                        edit_distance_banded(a.as_bytes(), b.as_bytes(), band),
+                        Some(expected).filter(|&d| d <= band)
+                    );
+                }
+            }
+        }
+        assert_eq!(edit_distance_banded(b"ab", b"abc", usize::MAX), Some(1));
+    }
+
+    #[test]
+    fn test_render() {
+        let (a, b): (Vec<char>, Vec<char>) =
+            ("kitten".chars().collect(), "sitting".chars().collect());
+        let alignment = align(&a, &b);
+        assert_eq!(alignment.distance, 3);
+        assert_eq!(
+            alignment.render(&a, &b),
+            ["kitten-", " ||| | ", "sitting"].join("\n")
+        );
+
+        let (a, b): (Vec<char>, Vec<char>) = ("café".chars().collect(), "cafe".chars().collect());
+        assert_eq!(
+            align(&a, &b).render(&a, &b),
+            ["café", "||| ", "cafe"].join("\n")
+        );
+
+        let (a, b) = ([10, 2, 3], [1, 2, 300]);
+        assert_eq!(
+            align(&a, &b).render(&a, &b),
+            ["1023  ", "  |   ", "1 2300"].join("\n")
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::{error::Error, io::prelude::*};
+
+/// The `n`-th Fibonacci number with `fib(0) = 0` and `fib(1) = 1`, by plain
+/// recursion in exponential time.
+pub fn fib_recursive(n: usize) -> u64 {
+    if n < 2 {
+        n as u64
+    } else {
+        fib_recursive(n - 1) + fib_recursive(n - 2)
+    }
+}
+
+/// The same recursion, computing every value once.
+pub fn fib_memo(n: usize) -> u64 {
+    fn inner(n: usize, memo: &mut Vec<Option<u64>>) -> u64 {
+        if let Some(x) = memo[n] {
+            return x;
+        }
+        let x = if n < 2 {
+            n as u64
+        } else {
+            inner(n - 1, memo) + inner(n - 2, memo)
+        };
+        memo[n] = Some(x);
+        x
+    }
+
+    inner(n, &mut vec![None; n + 1])
+}
+
+/// Bottom-up over a table of every value up to `n`. Overflows past `n = 93`.
+pub fn fib_table(n: usize) -> u64 {
+    let mut table = vec![0; (n + 1).max(2)];
+    table[1] = 1;
+    for i in 2..=n {
+        table[i] = table[i - 1] + table[i - 2];
+    }
+    table[n]
+}
+
+/// ALDS1_10_A, which counts from `fib(0) = fib(1) = 1`.
+pub fn input_fibonacci(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let mut buf = String::new();
+    reader.read_to_string(&mut buf)?;
+    let n: usize = buf.trim().parse()?;
+    writeln!(writer, "{}", fib_table(n + 1))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_fibonacci(&mut "3".as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "3\n");
+    }
+
+    #[test]
+    fn test_recursive() {
+        for n in 0..25 {
+            let expected = fib_recursive(n);
+            assert_eq!(fib_memo(n), expected);
+            assert_eq!(fib_table(n), expected);
+        }
+        assert_eq!(fib_table(93), 12_200_160_415_121_876_738);
+        assert_eq!(fib_memo(93), fib_table(93));
+    }
+}
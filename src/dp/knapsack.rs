@@ -0,0 +1,368 @@
+use std::{
+    error::Error,
+    io::{prelude::*, BufReader},
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Item {
+    pub value: usize,
+    pub weight: usize,
+}
+
+/// A best packing of a knapsack.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Knapsack {
+    pub value: usize,
+    pub weight: usize,
+    /// How many copies of every item are packed.
+    pub counts: Vec<usize>,
+}
+
+impl Knapsack {
+    fn from_counts(items: &[Item], counts: Vec<usize>) -> Self {
+        let (value, weight) = items
+            .iter()
+            .zip(&counts)
+            .fold((0, 0), |(v, w), (item, &c)| {
+                (v + item.value * c, w + item.weight * c)
+            });
+        Knapsack {
+            value,
+            weight,
+            counts,
+        }
+    }
+}
+
+/// 0-1 knapsack over the capacities in O(n * capacity) time. Keeps one row of
+/// best values and a bit per item and capacity for the choices, so the memory
+/// is O(capacity + n * capacity / 64) words.
+pub fn knapsack_01(items: &[Item], capacity: usize) -> Knapsack {
+    const BITS: usize = 64;
+    let words = capacity / BITS + 1;
+    // best[w] is the best value of the items so far within weight w, and bit w
+    // of taken[i] is set if item i improved it
+    let mut best = vec![0; capacity + 1];
+    let mut taken = vec![vec![0u64; words]; items.len()];
+    for (item, taken) in items.iter().zip(&mut taken) {
+        for w in (item.weight..=capacity).rev() {
+            let value = best[w - item.weight] + item.value;
+            if value > best[w] {
+                best[w] = value;
+                taken[w / BITS] |= 1 << (w % BITS);
+            }
+        }
+    }
+
+    let mut counts = vec![0; items.len()];
+    let mut w = capacity;
+    for (i, item) in items.iter().enumerate().rev() {
+        if taken[i][w / BITS] >> (w % BITS) & 1 == 1 {
+            counts[i] = 1;
+            w -= item.weight;
+        }
+    }
+    Knapsack::from_counts(items, counts)
+}
+
+/// 0-1 knapsack over the total values in O(n * sum of values), for capacities
+/// too large to tabulate.
+pub fn knapsack_01_by_value(items: &[Item], capacity: usize) -> Knapsack {
+    let total: usize = items.iter().map(|item| item.value).sum();
+    // table[i][v] is the least weight of the first i items reaching value v exactly
+    let mut table = vec![vec![None; total + 1]; items.len() + 1];
+    table[0][0] = Some(0);
+    for (i, item) in items.iter().enumerate() {
+        for v in 0..=total {
+            let mut best = table[i][v];
+            if item.value <= v {
+                if let Some(w) = table[i][v - item.value] {
+                    let w: usize = w + item.weight;
+                    if best.is_none_or(|b| w < b) {
+                        best = Some(w);
+                    }
+                }
+            }
+            table[i + 1][v] = best;
+        }
+    }
+
+    let mut v = (0..=total)
+        .rev()
+        .find(|&v| table[items.len()][v].is_some_and(|w| w <= capacity))
+        .unwrap();
+    let mut counts = vec![0; items.len()];
+    for (i, item) in items.iter().enumerate().rev() {
+        if table[i + 1][v] != table[i][v] {
+            counts[i] = 1;
+            v -= item.value;
+        }
+    }
+    Knapsack::from_counts(items, counts)
+}
+
+/// Knapsack with unlimited copies of every item in O(n * capacity). `None` if an
+/// item of weight 0 has a positive value, as the value is then unbounded; items
+/// of weight and value 0 are never packed.
+pub fn knapsack_unbounded(items: &[Item], capacity: usize) -> Option<Knapsack> {
+    if items.iter().any(|item| item.weight == 0 && item.value > 0) {
+        return None;
+    }
+
+    // best[w] is the best value within weight w, and last[w] the item packed
+    // last for it, if any
+    let mut best = vec![0; capacity + 1];
+    let mut last = vec![None; capacity + 1];
+    for w in 1..=capacity {
+        best[w] = best[w - 1];
+        for (i, item) in items.iter().enumerate().filter(|(_, item)| item.weight > 0) {
+            if item.weight <= w && best[w - item.weight] + item.value > best[w] {
+                best[w] = best[w - item.weight] + item.value;
+                last[w] = Some(i);
+            }
+        }
+    }
+
+    let mut counts = vec![0; items.len()];
+    let mut w = capacity;
+    while w > 0 {
+        match last[w] {
+            Some(i) => {
+                counts[i] += 1;
+                w -= items[i].weight;
+            }
+            None => w -= 1,
+        }
+    }
+    Some(Knapsack::from_counts(items, counts))
+}
+
+/// Knapsack with at most `limits[i]` copies of item `i` in
+/// O(capacity * sum of log limits), splitting every item into bundles of
+/// 1, 2, 4, ... copies and solving the 0-1 knapsack over the bundles. The
+/// choices take a bit per bundle and capacity.
+pub fn knapsack_bounded(items: &[Item], limits: &[usize], capacity: usize) -> Knapsack {
+    assert_eq!(items.len(), limits.len());
+
+    // (item, copies in the bundle)
+    let mut origins = vec![];
+    let mut bundles = vec![];
+    for (i, (item, &limit)) in items.iter().zip(limits).enumerate() {
+        let mut rest = limit;
+        let mut size = 1;
+        while rest > 0 {
+            let k = size.min(rest);
+            origins.push((i, k));
+            bundles.push(Item {
+                value: item.value * k,
+                weight: item.weight * k,
+            });
+            rest -= k;
+            size *= 2;
+        }
+    }
+
+    let packed = knapsack_01(&bundles, capacity);
+    let mut counts = vec![0; items.len()];
+    for (&(i, k), &c) in origins.iter().zip(&packed.counts) {
+        counts[i] += k * c;
+    }
+    Knapsack::from_counts(items, counts)
+}
+
+/// Reads `N W` and `N` lines of numbers, the first two being `v w`, returning
+/// every line including the first.
+fn read_rows(reader: &mut impl Read) -> Result<Vec<Vec<usize>>, Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader
+        .lines()
+        .map(|line| -> Result<Vec<usize>, Box<dyn Error>> {
+            Ok(line?
+                .split_whitespace()
+                .map(|x| x.parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()?)
+        });
+    let head = lines.next().ok_or("unexpected end of input")??;
+    let n = head[0];
+    std::iter::once(Ok(head)).chain(lines.take(n)).collect()
+}
+
+fn to_items(rows: &[Vec<usize>]) -> Vec<Item> {
+    rows.iter()
+        .map(|row| Item {
+            value: row[0],
+            weight: row[1],
+        })
+        .collect()
+}
+
+/// DPL_1_B: prints the best value of a 0-1 knapsack.
+pub fn input_knapsack_01(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let rows = read_rows(reader)?;
+    let (capacity, rows) = (rows[0][1], &rows[1..]);
+    writeln!(writer, "{}", knapsack_01(&to_items(rows), capacity).value)?;
+    Ok(())
+}
+
+/// DPL_1_C: prints the best value with unlimited copies of every item.
+pub fn input_knapsack_unbounded(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let rows = read_rows(reader)?;
+    let (capacity, rows) = (rows[0][1], &rows[1..]);
+    let packed = knapsack_unbounded(&to_items(rows), capacity).ok_or("unbounded value")?;
+    writeln!(writer, "{}", packed.value)?;
+    Ok(())
+}
+
+/// DPL_1_F: prints the best value of a 0-1 knapsack with a huge capacity.
+pub fn input_knapsack_large_weight(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let rows = read_rows(reader)?;
+    let (capacity, rows) = (rows[0][1], &rows[1..]);
+    writeln!(
+        writer,
+        "{}",
+        knapsack_01_by_value(&to_items(rows), capacity).value
+    )?;
+    Ok(())
+}
+
+/// DPL_1_G: items given as `v w m`; prints the best value taking at most `m`
+/// copies of every item.
+pub fn input_knapsack_bounded(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let rows = read_rows(reader)?;
+    let (capacity, rows) = (rows[0][1], &rows[1..]);
+    let limits = rows.iter().map(|row| row[2]).collect::<Vec<_>>();
+    writeln!(
+        writer,
+        "{}",
+        knapsack_bounded(&to_items(rows), &limits, capacity).value
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    macro_rules! run {
+        ($f:ident, $input:expr) => {{
+            let input = $input.join("\n");
+            let mut output: Vec<u8> = vec![];
+            assert!($f(&mut input.as_bytes(), &mut output).is_ok());
+            String::from_utf8(output).unwrap()
+        }};
+    }
+
+    #[test]
+    fn test1() {
+        let input = ["4 5", "4 2", "5 2", "2 1", "8 3"];
+        assert_eq!(run!(input_knapsack_01, &input), "13\n");
+        assert_eq!(run!(input_knapsack_large_weight, &input), "13\n");
+
+        let input = ["4 8", "4 2", "5 2", "2 1", "8 3"];
+        assert_eq!(run!(input_knapsack_unbounded, &input), "21\n");
+
+        let input = ["4 8", "4 3 2", "2 1 1", "1 2 4", "3 2 2"];
+        assert_eq!(run!(input_knapsack_bounded, &input), "12\n");
+    }
+
+    /// Best value over every choice of counts within `limits`.
+    fn exhaustive(items: &[Item], limits: &[usize], capacity: usize) -> usize {
+        match items.split_first() {
+            None => 0,
+            Some((item, rest)) => (0..=limits[0])
+                .take_while(|&c| c * item.weight <= capacity)
+                .map(|c| {
+                    c * item.value + exhaustive(rest, &limits[1..], capacity - c * item.weight)
+                })
+                .max()
+                .unwrap(),
+        }
+    }
+
+    fn assert_packing(packed: &Knapsack, items: &[Item], limits: &[usize], capacity: usize) {
+        assert_eq!(packed, &Knapsack::from_counts(items, packed.counts.clone()));
+        assert!(packed.weight <= capacity);
+        assert!(packed.counts.iter().zip(limits).all(|(c, l)| c <= l));
+        assert_eq!(packed.value, exhaustive(items, limits, capacity));
+    }
+
+    #[test]
+    fn test_exhaustive() {
+        let mut rng = XorShift::new(1);
+        for _ in 0..50 {
+            let n = rng.below(7);
+            let items = (0..n)
+                .map(|_| Item {
+                    value: rng.below(20),
+                    weight: rng.below(8) + 1,
+                })
+                .collect::<Vec<_>>();
+            let capacity = rng.below(25);
+
+            let ones = vec![1; n];
+            assert_packing(&knapsack_01(&items, capacity), &items, &ones, capacity);
+            assert_packing(
+                &knapsack_01_by_value(&items, capacity),
+                &items,
+                &ones,
+                capacity,
+            );
+
+            let limits = (0..n).map(|_| rng.below(6)).collect::<Vec<_>>();
+            let packed = knapsack_bounded(&items, &limits, capacity);
+            assert_packing(&packed, &items, &limits, capacity);
+
+            let unlimited = vec![capacity; n];
+            let packed = knapsack_unbounded(&items, capacity).unwrap();
+            assert_packing(&packed, &items, &unlimited, capacity);
+        }
+    }
+
+    #[test]
+    fn test_unbounded_zero_weight() {
+        let mut items = vec![
+            Item {
+                value: 0,
+                weight: 0,
+            },
+            Item {
+                value: 3,
+                weight: 2,
+            },
+        ];
+        let packed = knapsack_unbounded(&items, 5).unwrap();
+        assert_eq!((packed.value, packed.counts), (6, vec![0, 2]));
+
+        items[0].value = 1;
+        assert_eq!(knapsack_unbounded(&items, 5), None);
+    }
+
+    #[test]
+    fn test_bounded_limits() {
+        // DPL_1_G bounds: 100 items of up to 10^4 copies, capacity 10^4
+        let items = (0..100)
+            .map(|i| Item {
+                value: 1000 + i,
+                weight: 100 + i,
+            })
+            .collect::<Vec<_>>();
+        let limits = vec![10_000; 100];
+        let packed = knapsack_bounded(&items, &limits, 10_000);
+        assert!(packed.weight <= 10_000);
+        // 100 copies of the item with the best ratio fill the knapsack exactly
+        assert_eq!(packed.value, 100_000);
+    }
+}
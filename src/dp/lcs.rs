@@ -0,0 +1,126 @@
+use std::{
+    error::Error,
+    io::{prelude::*, BufReader},
+};
+
+/// Length of a longest common subsequence by plain recursion in exponential time.
+pub fn lcs_length_recursive<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    match (a.split_last(), b.split_last()) {
+        (Some((x, a_rest)), Some((y, b_rest))) => {
+            if x == y {
+                lcs_length_recursive(a_rest, b_rest) + 1
+            } else {
+                lcs_length_recursive(a_rest, b).max(lcs_length_recursive(a, b_rest))
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// `table[i][j]` is the LCS length of `a[..i]` and `b[..j]`.
+fn lcs_table<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Vec<usize>> {
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            table[i + 1][j + 1] = if x == y {
+                table[i][j] + 1
+            } else {
+                table[i][j + 1].max(table[i + 1][j])
+            };
+        }
+    }
+    table
+}
+
+/// Length of a longest common subsequence in O(nm) time and O(m) space.
+pub fn lcs_length<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut row = vec![0; b.len() + 1];
+    for x in a {
+        // the value of `row[j]` from the previous row
+        let mut diagonal = 0;
+        for (j, y) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if x == y {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// A longest common subsequence, rebuilt by walking the DP table back from the end.
+pub fn lcs<T: PartialEq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    let table = lcs_table(a, b);
+    let (mut i, mut j) = (a.len(), b.len());
+    let mut result = Vec::with_capacity(table[i][j]);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            result.push(a[i - 1].clone());
+            i -= 1;
+            j -= 1;
+        } else if table[i - 1][j] >= table[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    result.reverse();
+    result
+}
+
+/// ALDS1_10_C: `q`, then `q` pairs of lines; prints the LCS length of every pair.
+pub fn input_lcs(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let q: usize = lines.next().unwrap()?.trim().parse()?;
+    for _ in 0..q {
+        let a = lines.next().ok_or("unexpected end of input")??;
+        let b = lines.next().ok_or("unexpected end of input")??;
+        writeln!(
+            writer,
+            "{}",
+            lcs_length(a.trim().as_bytes(), b.trim().as_bytes())
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let input = ["3", "abcbdab", "bdcaba", "abc", "abc", "abc", "bc"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_lcs(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "4\n3\n2\n");
+    }
+
+    #[test]
+    fn test_recursive() {
+        let words = ["", "a", "abcbdab", "bdcaba", "xyz", "aaba", "baab"];
+        for a in &words {
+            for b in &words {
+                let (a, b) = (a.as_bytes(), b.as_bytes());
+                let expected = lcs_length_recursive(a, b);
+                assert_eq!(lcs_length(a, b), expected);
+
+                // the result is a subsequence of both
+                let common = lcs(a, b);
+                assert_eq!(common.len(), expected);
+                for s in &[a, b] {
+                    let mut rest = s.iter();
+                    assert!(common.iter().all(|c| rest.any(|x| x == c)));
+                }
+            }
+        }
+        assert_eq!(lcs(b"abcbdab", b"bdcaba"), b"bcba".to_vec());
+    }
+}
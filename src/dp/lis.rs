@@ -0,0 +1,98 @@
+use crate::search::binary_search::BinarySearch;
+use std::{
+    error::Error,
+    io::{prelude::*, BufReader},
+};
+
+/// Length of a longest strictly increasing subsequence in O(n^2).
+pub fn lis_length_quadratic<T: Ord>(seq: &[T]) -> usize {
+    // ending[i] is the length of the longest one ending at seq[i]
+    let mut ending = vec![1; seq.len()];
+    for i in 0..seq.len() {
+        for j in 0..i {
+            if seq[j] < seq[i] {
+                ending[i] = ending[i].max(ending[j] + 1);
+            }
+        }
+    }
+    ending.into_iter().max().unwrap_or(0)
+}
+
+/// A longest strictly increasing subsequence in O(n log n).
+///
+/// Keeps the smallest possible last element of an increasing subsequence of
+/// every length, and finds where each element goes by `lower_bound`.
+pub fn lis<T: Ord + Clone>(seq: &[T]) -> Vec<T> {
+    // tails[k] is the least last element of an increasing subsequence of length
+    // k + 1, and tail_ids[k] its index in seq
+    let mut tails: Vec<T> = vec![];
+    let mut tail_ids = vec![];
+    let mut parents = vec![None; seq.len()];
+    for (i, x) in seq.iter().enumerate() {
+        let k = tails.lower_bound(x);
+        if k > 0 {
+            parents[i] = Some(tail_ids[k - 1]);
+        }
+        if k == tails.len() {
+            tails.push(x.clone());
+            tail_ids.push(i);
+        } else {
+            tails[k] = x.clone();
+            tail_ids[k] = i;
+        }
+    }
+
+    let mut result = Vec::with_capacity(tails.len());
+    let mut cur = tail_ids.last().copied();
+    while let Some(i) = cur {
+        result.push(seq[i].clone());
+        cur = parents[i];
+    }
+    result.reverse();
+    result
+}
+
+/// DPL_1_D: `n` and `n` lines of numbers; prints the length of a longest
+/// increasing subsequence.
+pub fn input_lis(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let n: usize = lines.next().unwrap()?.trim().parse()?;
+    let seq = lines
+        .take(n)
+        .map(|line| -> Result<isize, Box<dyn Error>> { Ok(line?.trim().parse()?) })
+        .collect::<Result<Vec<_>, _>>()?;
+    writeln!(writer, "{}", lis(&seq).len())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test1() {
+        let input = ["5", "5", "1", "3", "2", "4"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_lis(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "3\n");
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let mut rng = XorShift::new(1);
+        for n in 0..40 {
+            let seq = (0..n).map(|_| rng.below(20)).collect::<Vec<_>>();
+            let result = lis(&seq);
+            assert_eq!(result.len(), lis_length_quadratic(&seq));
+            assert!(result.windows(2).all(|w| w[0] < w[1]));
+            let mut rest = seq.iter();
+            assert!(result.iter().all(|x| rest.any(|y| y == x)));
+        }
+        assert_eq!(lis(&[3, 1, 4, 1, 5, 9, 2, 6]), vec![1, 4, 5, 6]);
+    }
+}
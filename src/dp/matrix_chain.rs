@@ -0,0 +1,140 @@
+use std::{
+    error::Error,
+    io::{prelude::*, BufReader},
+};
+
+/// Minimum number of scalar multiplications for a chain of matrices where the
+/// `i`-th matrix is `dims[i] x dims[i + 1]`, by plain recursion in exponential time.
+pub fn matrix_chain_recursive(dims: &[usize]) -> usize {
+    if dims.len() <= 2 {
+        return 0;
+    }
+    let last = dims.len() - 1;
+    (1..last)
+        .map(|k| {
+            matrix_chain_recursive(&dims[..=k])
+                + matrix_chain_recursive(&dims[k..])
+                + dims[0] * dims[k] * dims[last]
+        })
+        .min()
+        .unwrap()
+}
+
+/// Cheapest way to multiply a chain of matrices.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MatrixChainOrder {
+    pub cost: usize,
+    /// `split[i][j]` is the `k` with which the product of matrices `i..=j` is
+    /// best split into `i..=k` and `k + 1..=j`.
+    split: Vec<Vec<usize>>,
+}
+
+impl MatrixChainOrder {
+    /// The optimal order with matrices named `A1, A2, ...`, e.g. `((A1A2)A3)`.
+    pub fn parenthesize(&self) -> String {
+        fn inner(split: &[Vec<usize>], i: usize, j: usize, result: &mut String) {
+            if i == j {
+                result.push_str(&format!("A{}", i + 1));
+                return;
+            }
+            let k = split[i][j];
+            result.push('(');
+            inner(split, i, k, result);
+            inner(split, k + 1, j, result);
+            result.push(')');
+        }
+
+        let mut result = String::new();
+        if !self.split.is_empty() {
+            inner(&self.split, 0, self.split.len() - 1, &mut result);
+        }
+        result
+    }
+}
+
+/// Bottom-up over chains of increasing length in O(n^3), where `dims` holds
+/// the `n + 1` dimensions of `n` matrices.
+pub fn matrix_chain_order(dims: &[usize]) -> MatrixChainOrder {
+    let n = dims.len().saturating_sub(1);
+    // cost[i][j] for the product of matrices i..=j
+    let mut cost = vec![vec![0; n]; n];
+    let mut split = vec![vec![0; n]; n];
+    for len in 2..=n {
+        for i in 0..=n - len {
+            let j = i + len - 1;
+            let (k, c) = (i..j)
+                .map(|k| {
+                    (
+                        k,
+                        cost[i][k] + cost[k + 1][j] + dims[i] * dims[k + 1] * dims[j + 1],
+                    )
+                })
+                .min_by_key(|&(_, c)| c)
+                .unwrap();
+            cost[i][j] = c;
+            split[i][j] = k;
+        }
+    }
+
+    MatrixChainOrder {
+        cost: if n == 0 { 0 } else { cost[0][n - 1] },
+        split,
+    }
+}
+
+/// ALDS1_10_B: `n`, then the rows and columns of `n` matrices.
+pub fn input_matrix_chain(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let n: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut dims = Vec::with_capacity(n + 1);
+    for line in lines.take(n) {
+        let values = line?
+            .split_whitespace()
+            .map(|x| x.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()?;
+        if dims.is_empty() {
+            dims.push(values[0]);
+        }
+        dims.push(values[1]);
+    }
+    writeln!(writer, "{}", matrix_chain_order(&dims).cost)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test1() {
+        let input = ["6", "30 35", "35 15", "15 5", "5 10", "10 20", "20 25"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_matrix_chain(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "15125\n");
+    }
+
+    #[test]
+    fn test_recursive() {
+        let mut rng = XorShift::new(1);
+        for n in 1..10 {
+            let dims = (0..=n).map(|_| rng.below(50) + 1).collect::<Vec<_>>();
+            assert_eq!(
+                matrix_chain_order(&dims).cost,
+                matrix_chain_recursive(&dims)
+            );
+        }
+
+        let order = matrix_chain_order(&[30, 35, 15, 5, 10, 20, 25]);
+        assert_eq!(order.parenthesize(), "((A1(A2A3))((A4A5)A6))");
+        assert_eq!(matrix_chain_order(&[3, 4]).parenthesize(), "A1");
+        assert_eq!(matrix_chain_order(&[]).cost, 0);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod closest_pair;
+pub mod point;
+pub mod polygon;
+pub mod segment;
@@ -0,0 +1,172 @@
+use super::point::{read_lines, Point, PointF};
+use std::{cmp::Ordering, error::Error, io::prelude::*};
+
+/// The closest pair of points and their distance.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ClosestPair {
+    pub distance: f64,
+    pub pair: (PointF, PointF),
+}
+
+impl ClosestPair {
+    fn new(a: &PointF, b: &PointF) -> Self {
+        ClosestPair {
+            distance: a.distance(b),
+            pair: (*a, *b),
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other.distance < self.distance {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+/// Finds the closest pair in O(n log n) by divide and conquer, merging the halves
+/// by y while recursing as `merge_sort` does. Returns `None` for fewer than two points.
+pub fn closest_pair(points: &[PointF]) -> Option<ClosestPair> {
+    fn by_y(a: &PointF, b: &PointF) -> bool {
+        a.y <= b.y
+    }
+
+    fn merge(seq: &mut [PointF], mid: usize) {
+        let left = seq[..mid].to_vec();
+        let right = seq[mid..].to_vec();
+        let (mut i, mut j) = (0, 0);
+        for x in seq.iter_mut() {
+            *x = if j == right.len() || (i < left.len() && by_y(&left[i], &right[j])) {
+                i += 1;
+                left[i - 1]
+            } else {
+                j += 1;
+                right[j - 1]
+            };
+        }
+    }
+
+    /// Expects `seq` sorted by x and leaves it sorted by y.
+    fn inner(seq: &mut [PointF]) -> Option<ClosestPair> {
+        let n = seq.len();
+        if n < 2 {
+            return None;
+        }
+
+        let mid = n / 2;
+        let mid_x = seq[mid].x;
+        let (left, right) = seq.split_at_mut(mid);
+        let best = match (inner(left), inner(right)) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        merge(seq, mid);
+
+        // candidates within the current best distance of the dividing line, by y
+        let mut best = best;
+        let mut strip: Vec<PointF> = vec![];
+        for p in seq.iter() {
+            let d = best.map_or(f64::INFINITY, |b| b.distance);
+            if (p.x - mid_x).abs() >= d {
+                continue;
+            }
+            for q in strip.iter().rev() {
+                if p.y - q.y >= best.map_or(f64::INFINITY, |b| b.distance) {
+                    break;
+                }
+                let candidate = ClosestPair::new(q, p);
+                best = Some(best.map_or(candidate, |b| b.min(candidate)));
+            }
+            strip.push(*p);
+        }
+
+        best
+    }
+
+    let mut seq = points.to_vec();
+    seq.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(Ordering::Equal));
+    inner(&mut seq)
+}
+
+/// O(n^2) reference implementation.
+pub fn closest_pair_brute_force(points: &[PointF]) -> Option<ClosestPair> {
+    let mut best: Option<ClosestPair> = None;
+    for (i, a) in points.iter().enumerate() {
+        for b in &points[i + 1..] {
+            let candidate = ClosestPair::new(a, b);
+            best = Some(best.map_or(candidate, |b| b.min(candidate)));
+        }
+    }
+    best
+}
+
+pub fn input_closest_pair(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let lines = read_lines(reader)?;
+    let n = lines[0][0] as usize;
+    let points: Vec<PointF> = lines[1..=n]
+        .iter()
+        .map(|l| Point::new(l[0], l[1]))
+        .collect();
+
+    let result = closest_pair(&points).ok_or("at least two points are required")?;
+    writeln!(writer, "{:.10}", result.distance)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test1() {
+        let input = ["2", "0.0 0.0", "1.0 0.0"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_closest_pair(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "1.0000000000\n");
+    }
+
+    #[test]
+    fn test2() {
+        let input = ["3", "0.0 0.0", "2.0 0.0", "1.0 1.0"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_closest_pair(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "1.4142135624\n");
+    }
+
+    #[test]
+    fn test_brute_force() {
+        let mut rng = XorShift::new(42);
+        let mut next = || rng.below(1 << 24) as f64 / 1000.;
+
+        for n in 0..200 {
+            let points: Vec<PointF> = (0..n).map(|_| Point::new(next(), next())).collect();
+            let expected = closest_pair_brute_force(&points);
+            let actual = closest_pair(&points);
+
+            match (expected, actual) {
+                (Some(e), Some(a)) => {
+                    assert_eq!(e.distance, a.distance);
+                    assert_eq!(a.pair.0.distance(&a.pair.1), a.distance);
+                }
+                (None, None) => assert!(n < 2),
+                _ => panic!("{:?} {:?}", expected, actual),
+            }
+        }
+
+        // duplicated points are at distance 0
+        let points = vec![Point::new(1., 1.), Point::new(5., 5.), Point::new(1., 1.)];
+        assert_eq!(closest_pair(&points).unwrap().distance, 0.);
+    }
+}
@@ -0,0 +1,237 @@
+pub use crate::recursive::koch_curve::Point;
+use itertools::Itertools;
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    io::{prelude::*, BufReader},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+pub type PointF = Point<f64>;
+
+pub const EPS: f64 = 1e-10;
+
+impl<T> Add for Point<T>
+where
+    T: Add<Output = T> + PartialEq + Clone + Debug,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T> Sub for Point<T>
+where
+    T: Sub<Output = T> + PartialEq + Clone + Debug,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> Neg for Point<T>
+where
+    T: Neg<Output = T> + PartialEq + Clone + Debug,
+{
+    type Output = Self;
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T> Mul<T> for Point<T>
+where
+    T: Mul<Output = T> + PartialEq + Clone + Debug,
+{
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Point::new(self.x * rhs.clone(), self.y * rhs)
+    }
+}
+
+impl<T> Div<T> for Point<T>
+where
+    T: Div<Output = T> + PartialEq + Clone + Debug,
+{
+    type Output = Self;
+    fn div(self, rhs: T) -> Self {
+        Point::new(self.x / rhs.clone(), self.y / rhs)
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Mul<Output = T> + Add<Output = T> + Sub<Output = T> + PartialEq + Copy + Debug,
+{
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the cross product; positive when `other` is counterclockwise.
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn norm2(&self) -> T {
+        self.dot(self)
+    }
+}
+
+impl Point<f64> {
+    pub fn norm(&self) -> f64 {
+        self.norm2().sqrt()
+    }
+
+    pub fn distance(&self, other: &Self) -> f64 {
+        (*self - *other).norm()
+    }
+
+    /// Rotates counterclockwise around the origin by `angle` radians.
+    pub fn rotate(&self, angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Point::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// The angle from the x axis in radians, in (-pi, pi].
+    pub fn arg(&self) -> f64 {
+        self.y.atan2(self.x)
+    }
+}
+
+/// Position of `p2` relative to the directed segment `p0` -> `p1`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Ccw {
+    CounterClockwise,
+    Clockwise,
+    /// On the line, behind `p0`.
+    OnlineBack,
+    /// On the line, beyond `p1`.
+    OnlineFront,
+    OnSegment,
+}
+
+impl Display for Ccw {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Ccw::CounterClockwise => "COUNTER_CLOCKWISE",
+            Ccw::Clockwise => "CLOCKWISE",
+            Ccw::OnlineBack => "ONLINE_BACK",
+            Ccw::OnlineFront => "ONLINE_FRONT",
+            Ccw::OnSegment => "ON_SEGMENT",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl Ccw {
+    /// The classic integer encoding: 1, -1, 2, -2 and 0 in declaration order.
+    pub fn sign(&self) -> i32 {
+        match self {
+            Ccw::CounterClockwise => 1,
+            Ccw::Clockwise => -1,
+            Ccw::OnlineBack => 2,
+            Ccw::OnlineFront => -2,
+            Ccw::OnSegment => 0,
+        }
+    }
+}
+
+pub fn ccw(p0: &PointF, p1: &PointF, p2: &PointF) -> Ccw {
+    let a = *p1 - *p0;
+    let b = *p2 - *p0;
+    let cross = a.cross(&b);
+
+    if cross > EPS {
+        Ccw::CounterClockwise
+    } else if cross < -EPS {
+        Ccw::Clockwise
+    } else if a.dot(&b) < -EPS {
+        Ccw::OnlineBack
+    } else if a.norm2() < b.norm2() {
+        Ccw::OnlineFront
+    } else {
+        Ccw::OnSegment
+    }
+}
+
+/// Reads whitespace separated numbers from every line.
+pub(crate) fn read_lines(reader: &mut impl Read) -> Result<Vec<Vec<f64>>, Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut result = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        let values = line
+            .split_whitespace()
+            .map(|x| x.parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()?;
+        if !values.is_empty() {
+            result.push(values);
+        }
+    }
+    Ok(result)
+}
+
+pub fn input_ccw(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let lines = read_lines(reader)?;
+    let head = &lines[0];
+    let p0 = Point::new(head[0], head[1]);
+    let p1 = Point::new(head[2], head[3]);
+
+    let result = lines[2..]
+        .iter()
+        .map(|l| ccw(&p0, &p1, &Point::new(l[0], l[1])).to_string())
+        .join("\n");
+    writeln!(writer, "{}", result)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_vector() {
+        let a = Point::new(1, 2);
+        let b = Point::new(3, -1);
+
+        assert_eq!(a + b, Point::new(4, 1));
+        assert_eq!(a - b, Point::new(-2, 3));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!(a.dot(&b), 1);
+        assert_eq!(a.cross(&b), -7);
+        assert_eq!(b.norm2(), 10);
+
+        let p = Point::new(3., 4.);
+        assert_eq!(p.norm(), 5.);
+        assert_eq!(p / 2., Point::new(1.5, 2.));
+        let r = p.rotate(FRAC_PI_2);
+        assert!((r.x + 4.).abs() < EPS && (r.y - 3.).abs() < EPS);
+    }
+
+    #[test]
+    fn test1() {
+        let input = ["0 0 2 0", "5", "-1 1", "-1 -1", "-1 0", "0 0", "3 0"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_ccw(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "COUNTER_CLOCKWISE",
+                "CLOCKWISE",
+                "ONLINE_BACK",
+                "ON_SEGMENT",
+                "ONLINE_FRONT",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+}
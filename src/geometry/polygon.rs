@@ -0,0 +1,248 @@
+use super::point::{read_lines, Point, PointF, EPS};
+use std::{cmp::Ordering, error::Error, io::prelude::*};
+
+/// Signed area of the polygon; positive when the vertices run counterclockwise.
+pub fn signed_area(polygon: &[PointF]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| polygon[i].cross(&polygon[(i + 1) % n]))
+        .sum::<f64>()
+        / 2.
+}
+
+pub fn area(polygon: &[PointF]) -> f64 {
+    signed_area(polygon).abs()
+}
+
+/// Whether the counterclockwise polygon is convex. Straight angles are allowed.
+pub fn is_convex(polygon: &[PointF]) -> bool {
+    let n = polygon.len();
+    (0..n).all(|i| {
+        let a = polygon[(i + 1) % n] - polygon[i];
+        let b = polygon[(i + 2) % n] - polygon[(i + 1) % n];
+        a.cross(&b) > -EPS
+    })
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Containment {
+    Outside,
+    OnEdge,
+    Inside,
+}
+
+/// Point-in-polygon test by counting crossings of a ray towards +x.
+pub fn contains(polygon: &[PointF], p: &PointF) -> Containment {
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let mut a = polygon[i] - *p;
+        let mut b = polygon[(i + 1) % n] - *p;
+
+        if a.cross(&b).abs() < EPS && a.dot(&b) < EPS {
+            return Containment::OnEdge;
+        }
+        if a.y > b.y {
+            std::mem::swap(&mut a, &mut b);
+        }
+        if a.y < EPS && EPS < b.y && a.cross(&b) > EPS {
+            inside = !inside;
+        }
+    }
+
+    if inside {
+        Containment::Inside
+    } else {
+        Containment::Outside
+    }
+}
+
+/// Convex hull by Andrew's monotone chain.
+///
+/// Points on the hull's edges are kept. The hull runs counterclockwise from the
+/// point with the smallest y coordinate, the leftmost one on ties.
+pub fn convex_hull(points: &[PointF]) -> Vec<PointF> {
+    fn chain<'a>(points: impl Iterator<Item = &'a PointF>) -> Vec<PointF> {
+        let mut hull: Vec<PointF> = vec![];
+        for p in points {
+            while hull.len() >= 2 {
+                let a = hull[hull.len() - 2];
+                let b = hull[hull.len() - 1];
+                if (b - a).cross(&(*p - b)) < -EPS {
+                    hull.pop();
+                } else {
+                    break;
+                }
+            }
+            hull.push(*p);
+        }
+        hull
+    }
+
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+    });
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut hull = chain(points.iter());
+    let (first, last) = (points[0], points[points.len() - 1]);
+    let collinear = points
+        .iter()
+        .all(|p| (last - first).cross(&(*p - first)).abs() < EPS);
+    // on collinear input the lower chain already holds every point, and the
+    // upper one would walk them back
+    if !collinear {
+        let upper = chain(points.iter().rev());
+        hull.extend(&upper[1..upper.len() - 1]);
+    }
+
+    let start = (0..hull.len())
+        .min_by(|&i, &j| {
+            let (a, b) = (hull[i], hull[j]);
+            a.y.partial_cmp(&b.y)
+                .unwrap_or(Ordering::Equal)
+                .then(a.x.partial_cmp(&b.x).unwrap_or(Ordering::Equal))
+        })
+        .unwrap();
+    if collinear && start > 0 {
+        // the start is the other end of the line
+        hull.reverse();
+    } else {
+        hull.rotate_left(start);
+    }
+    hull
+}
+
+/// Reads `n` followed by `n` lines of `x y`.
+fn read_polygon(lines: &[Vec<f64>]) -> Vec<PointF> {
+    let n = lines[0][0] as usize;
+    lines[1..=n]
+        .iter()
+        .map(|l| Point::new(l[0], l[1]))
+        .collect()
+}
+
+pub fn input_area(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let lines = read_lines(reader)?;
+    let polygon = read_polygon(&lines);
+    writeln!(writer, "{:.1}", area(&polygon))?;
+    Ok(())
+}
+
+pub fn input_is_convex(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let lines = read_lines(reader)?;
+    let polygon = read_polygon(&lines);
+    writeln!(writer, "{}", if is_convex(&polygon) { 1 } else { 0 })?;
+    Ok(())
+}
+
+pub fn input_contains(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let lines = read_lines(reader)?;
+    let polygon = read_polygon(&lines);
+
+    for l in &lines[polygon.len() + 2..] {
+        let code = match contains(&polygon, &Point::new(l[0], l[1])) {
+            Containment::Inside => 2,
+            Containment::OnEdge => 1,
+            Containment::Outside => 0,
+        };
+        writeln!(writer, "{}", code)?;
+    }
+    Ok(())
+}
+
+pub fn input_convex_hull(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let lines = read_lines(reader)?;
+    let points = read_polygon(&lines);
+
+    let hull = convex_hull(&points);
+    writeln!(writer, "{}", hull.len())?;
+    for p in hull {
+        writeln!(writer, "{} {}", p.x, p.y)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! run {
+        ($f:ident, $input:expr) => {{
+            let input = $input.join("\n");
+            let mut output: Vec<u8> = vec![];
+            assert!($f(&mut input.as_bytes(), &mut output).is_ok());
+            String::from_utf8(output).unwrap()
+        }};
+    }
+
+    #[test]
+    fn test_area_convex() {
+        assert_eq!(run!(input_area, &["3", "0 0", "2 2", "-1 1"]), "2.0\n");
+        assert_eq!(
+            run!(input_area, &["4", "0 0", "1 1", "1 2", "0 2"]),
+            "1.5\n"
+        );
+        assert_eq!(
+            run!(input_is_convex, &["4", "0 0", "3 1", "2 3", "0 3"]),
+            "1\n"
+        );
+        assert_eq!(
+            run!(input_is_convex, &["5", "0 0", "2 0", "1 1", "2 2", "0 2"]),
+            "0\n"
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        assert_eq!(
+            run!(
+                input_contains,
+                &["4", "0 0", "3 1", "2 3", "0 3", "3", "2 1", "0 2", "3 2"]
+            ),
+            "2\n1\n0\n"
+        );
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        assert_eq!(
+            run!(
+                input_convex_hull,
+                &["7", "2 1", "0 0", "1 2", "2 2", "4 2", "1 3", "3 3"]
+            ),
+            ["5", "0 0", "2 1", "4 2", "3 3", "1 3", ""].join("\n")
+        );
+        assert_eq!(
+            run!(input_convex_hull, &["4", "0 0", "2 2", "0 2", "0 1"]),
+            ["4", "0 0", "2 2", "0 2", "0 1", ""].join("\n")
+        );
+
+        let collinear: Vec<PointF> = (0..4).map(|i| Point::new(i as f64, 0.)).collect();
+        assert_eq!(convex_hull(&collinear).len(), 4);
+
+        let diagonal: Vec<PointF> = (0..100_000)
+            .rev()
+            .map(|i| Point::new(i as f64, -i as f64))
+            .collect();
+        let hull = convex_hull(&diagonal);
+        assert_eq!(hull.len(), 100_000);
+        assert_eq!(hull[0], Point::new(99_999., -99_999.));
+        assert_eq!(hull[1], Point::new(99_998., -99_998.));
+    }
+}
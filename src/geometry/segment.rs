@@ -0,0 +1,273 @@
+use super::point::{ccw, read_lines, Point, PointF, EPS};
+use itertools::Itertools;
+use std::{error::Error, io::prelude::*};
+
+/// A segment, or the line through `p1` and `p2` where noted.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Segment {
+    pub p1: PointF,
+    pub p2: PointF,
+}
+
+impl Segment {
+    pub fn new(p1: PointF, p2: PointF) -> Self {
+        Segment { p1, p2 }
+    }
+
+    fn from_slice(v: &[f64]) -> Self {
+        Segment::new(Point::new(v[0], v[1]), Point::new(v[2], v[3]))
+    }
+
+    fn direction(&self) -> PointF {
+        self.p2 - self.p1
+    }
+
+    /// Projection of `p` onto the line.
+    pub fn projection(&self, p: &PointF) -> PointF {
+        let base = self.direction();
+        let r = (*p - self.p1).dot(&base) / base.norm2();
+        self.p1 + base * r
+    }
+
+    /// Reflection of `p` across the line.
+    pub fn reflection(&self, p: &PointF) -> PointF {
+        *p + (self.projection(p) - *p) * 2.
+    }
+
+    pub fn is_parallel(&self, other: &Segment) -> bool {
+        self.direction().cross(&other.direction()).abs() < EPS
+    }
+
+    pub fn is_orthogonal(&self, other: &Segment) -> bool {
+        self.direction().dot(&other.direction()).abs() < EPS
+    }
+
+    pub fn intersects(&self, other: &Segment) -> bool {
+        ccw(&self.p1, &self.p2, &other.p1).sign() * ccw(&self.p1, &self.p2, &other.p2).sign() <= 0
+            && ccw(&other.p1, &other.p2, &self.p1).sign()
+                * ccw(&other.p1, &other.p2, &self.p2).sign()
+                <= 0
+    }
+
+    /// Intersection point of the two lines, or `None` if they are parallel.
+    pub fn cross_point(&self, other: &Segment) -> Option<PointF> {
+        let base = other.direction();
+        let d1 = base.cross(&(self.p1 - other.p1));
+        let d2 = base.cross(&(self.p2 - other.p1));
+        if (d1 - d2).abs() < EPS {
+            return None;
+        }
+        Some(self.p1 + self.direction() * (d1 / (d1 - d2)))
+    }
+
+    /// Distance from `p` to the segment.
+    pub fn distance_to_point(&self, p: &PointF) -> f64 {
+        let base = self.direction();
+        if base.dot(&(*p - self.p1)) < 0. {
+            return p.distance(&self.p1);
+        }
+        if (-base).dot(&(*p - self.p2)) < 0. {
+            return p.distance(&self.p2);
+        }
+        (base.cross(&(*p - self.p1)) / base.norm()).abs()
+    }
+
+    pub fn distance(&self, other: &Segment) -> f64 {
+        if self.intersects(other) {
+            return 0.;
+        }
+        self.distance_to_point(&other.p1)
+            .min(self.distance_to_point(&other.p2))
+            .min(other.distance_to_point(&self.p1))
+            .min(other.distance_to_point(&self.p2))
+    }
+}
+
+/// Reads a line `p1`-`p2` and answers every following query point with `f`.
+fn input_point_queries(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    f: impl Fn(&Segment, &PointF) -> PointF,
+) -> Result<(), Box<dyn Error>> {
+    let lines = read_lines(reader)?;
+    let s = Segment::from_slice(&lines[0]);
+
+    let result = lines[2..]
+        .iter()
+        .map(|l| f(&s, &Point::new(l[0], l[1])))
+        .map(|p| format!("{:.10} {:.10}", p.x, p.y))
+        .join("\n");
+    writeln!(writer, "{}", result)?;
+
+    Ok(())
+}
+
+/// Answers every query `x0 y0 x1 y1 x2 y2 x3 y3` with `f` applied to the two segments.
+fn input_segment_queries(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    f: impl Fn(&Segment, &Segment) -> String,
+) -> Result<(), Box<dyn Error>> {
+    let lines = read_lines(reader)?;
+
+    let result = lines[1..]
+        .iter()
+        .map(|l| f(&Segment::from_slice(&l[..4]), &Segment::from_slice(&l[4..])))
+        .join("\n");
+    writeln!(writer, "{}", result)?;
+
+    Ok(())
+}
+
+pub fn input_projection(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    input_point_queries(reader, writer, |s, p| s.projection(p))
+}
+
+pub fn input_reflection(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    input_point_queries(reader, writer, |s, p| s.reflection(p))
+}
+
+pub fn input_parallel_orthogonal(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    input_segment_queries(reader, writer, |s1, s2| {
+        if s1.is_parallel(s2) {
+            "2"
+        } else if s1.is_orthogonal(s2) {
+            "1"
+        } else {
+            "0"
+        }
+        .to_string()
+    })
+}
+
+pub fn input_intersection(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    input_segment_queries(reader, writer, |s1, s2| {
+        if s1.intersects(s2) { "1" } else { "0" }.to_string()
+    })
+}
+
+pub fn input_cross_point(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    input_segment_queries(reader, writer, |s1, s2| match s1.cross_point(s2) {
+        Some(p) => format!("{:.10} {:.10}", p.x, p.y),
+        None => "parallel".to_string(),
+    })
+}
+
+pub fn input_distance(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    input_segment_queries(reader, writer, |s1, s2| format!("{:.10}", s1.distance(s2)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! run {
+        ($f:ident, $input:expr) => {{
+            let input = $input.join("\n");
+            let mut output: Vec<u8> = vec![];
+            assert!($f(&mut input.as_bytes(), &mut output).is_ok());
+            String::from_utf8(output).unwrap()
+        }};
+    }
+
+    #[test]
+    fn test_projection_reflection() {
+        assert_eq!(
+            run!(input_projection, &["0 0 3 4", "1", "2 5"]),
+            "3.1200000000 4.1600000000\n"
+        );
+        assert_eq!(
+            run!(input_reflection, &["0 0 3 4", "3", "2 5", "1 4", "0 3"]),
+            [
+                "4.2400000000 3.3200000000",
+                "3.5600000000 2.0800000000",
+                "2.8800000000 0.8400000000",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(
+            run!(
+                input_parallel_orthogonal,
+                &["3", "0 0 3 0 0 2 3 2", "0 0 3 0 1 1 1 4", "0 0 3 0 1 1 2 2"]
+            ),
+            "2\n1\n0\n"
+        );
+        assert_eq!(
+            run!(
+                input_intersection,
+                &[
+                    "3",
+                    "0 0 3 0 1 1 2 -1",
+                    "0 0 3 0 3 1 3 -1",
+                    "0 0 3 0 3 -2 5 0"
+                ]
+            ),
+            "1\n1\n0\n"
+        );
+        assert_eq!(
+            run!(
+                input_cross_point,
+                &[
+                    "3",
+                    "0 0 2 0 1 1 1 -1",
+                    "0 0 1 1 0 1 1 0",
+                    "0 0 1 1 1 0 0 1"
+                ]
+            ),
+            [
+                "1.0000000000 0.0000000000",
+                "0.5000000000 0.5000000000",
+                "0.5000000000 0.5000000000",
+                ""
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            run!(
+                input_distance,
+                &[
+                    "3",
+                    "0 0 1 0 0 1 1 1",
+                    "0 0 1 0 2 1 1 2",
+                    "-1 0 1 0 0 1 0 -1"
+                ]
+            ),
+            "1.0000000000\n1.4142135624\n0.0000000000\n"
+        );
+    }
+
+    #[test]
+    fn test_collinear() {
+        let s1 = Segment::new(Point::new(0., 0.), Point::new(2., 0.));
+        let s2 = Segment::new(Point::new(1., 0.), Point::new(3., 0.));
+        let s3 = Segment::new(Point::new(3., 0.), Point::new(4., 0.));
+
+        assert!(s1.intersects(&s2));
+        assert!(!s1.intersects(&s3));
+        assert_eq!(s1.distance(&s3), 1.);
+        assert_eq!(s1.cross_point(&s2), None);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod connectivity;
+pub mod representation;
+pub mod shortest_path;
+pub mod spanning_tree;
+pub mod topological_sort;
+pub mod traversal;
@@ -0,0 +1,356 @@
+use super::{
+    representation::{AdjacencyList, Graph},
+    traversal::{dfs, neighbor_ids},
+};
+use crate::data_structure::union_find::UnionFind;
+use std::{
+    error::Error,
+    io::{prelude::*, BufReader},
+};
+
+/// Numbers the labels in order of first appearance.
+fn relabel(labels: &[usize]) -> Vec<usize> {
+    let mut map = vec![None; labels.len()];
+    let mut next = 0;
+    labels
+        .iter()
+        .map(|&l| {
+            *map[l].get_or_insert_with(|| {
+                next += 1;
+                next - 1
+            })
+        })
+        .collect()
+}
+
+/// Component of every node, ignoring edge directions. Components are numbered
+/// from 0 in order of their smallest node.
+pub fn connected_components(graph: &impl Graph) -> Vec<usize> {
+    let mut uf = UnionFind::new(graph.len());
+    for e in graph.edges() {
+        uf.union(e.from, e.to);
+    }
+    let roots: Vec<usize> = (0..graph.len()).map(|id| uf.find(id)).collect();
+    relabel(&roots)
+}
+
+/// Strongly connected components by Tarjan's algorithm, walked with an explicit
+/// stack. Components are numbered in reverse topological order: every edge
+/// between two components points to a smaller number.
+pub fn tarjan_scc(graph: &impl Graph) -> Vec<usize> {
+    let len = graph.len();
+    let neighbors = neighbor_ids(graph);
+    let mut order = vec![None; len];
+    let mut low = vec![0; len];
+    let mut on_stack = vec![false; len];
+    let mut stack = vec![];
+    let mut component = vec![0; len];
+    let (mut time, mut count) = (0, 0);
+
+    for start in 0..len {
+        if order[start].is_some() {
+            continue;
+        }
+        // (node, index of the next neighbor to look at)
+        let mut calls = vec![(start, 0)];
+        order[start] = Some(time);
+        low[start] = time;
+        time += 1;
+        stack.push(start);
+        on_stack[start] = true;
+
+        while let Some((id, next)) = calls.pop() {
+            if let Some(&v) = neighbors[id].get(next) {
+                calls.push((id, next + 1));
+                match order[v] {
+                    None => {
+                        order[v] = Some(time);
+                        low[v] = time;
+                        time += 1;
+                        stack.push(v);
+                        on_stack[v] = true;
+                        calls.push((v, 0));
+                    }
+                    Some(o) if on_stack[v] => low[id] = low[id].min(o),
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[id]);
+            }
+            if Some(low[id]) == order[id] {
+                while let Some(v) = stack.pop() {
+                    on_stack[v] = false;
+                    component[v] = count;
+                    if v == id {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+        }
+    }
+
+    component
+}
+
+/// Strongly connected components by Kosaraju's algorithm. Components are
+/// numbered in topological order: every edge between two components points to
+/// a larger number.
+pub fn kosaraju_scc(graph: &impl Graph) -> Vec<usize> {
+    let len = graph.len();
+    let mut transposed = AdjacencyList::new(len, true);
+    for e in graph.edges() {
+        transposed.add_edge(e.to, e.from, e.weight).unwrap();
+        if !graph.is_directed() {
+            transposed.add_edge(e.from, e.to, e.weight).unwrap();
+        }
+    }
+    let neighbors = neighbor_ids(&transposed);
+
+    let mut component = vec![None; len];
+    let mut count = 0;
+    for start in dfs(graph).finish_order().into_iter().rev() {
+        if component[start].is_some() {
+            continue;
+        }
+        component[start] = Some(count);
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            for &v in &neighbors[id] {
+                if component[v].is_none() {
+                    component[v] = Some(count);
+                    stack.push(v);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    component.into_iter().map(Option::unwrap).collect()
+}
+
+/// Bridges and articulation points of an undirected graph.
+fn lowlink(graph: &impl Graph) -> (Vec<(usize, usize)>, Vec<usize>) {
+    let len = graph.len();
+    let neighbors = neighbor_ids(graph);
+    let mut order = vec![None; len];
+    let mut low = vec![0; len];
+    let mut bridges = vec![];
+    let mut is_articulation = vec![false; len];
+    let mut time = 0;
+
+    for start in 0..len {
+        if order[start].is_some() {
+            continue;
+        }
+        order[start] = Some(time);
+        low[start] = time;
+        time += 1;
+        let mut children = 0;
+        // (node, parent, index of the next neighbor, whether the edge to the parent
+        // was skipped); a parallel edge to the parent is a back edge
+        let mut calls = vec![(start, None, 0, false)];
+
+        while let Some((id, parent, next, skipped)) = calls.pop() {
+            if let Some(&v) = neighbors[id].get(next) {
+                if Some(v) == parent && !skipped {
+                    calls.push((id, parent, next + 1, true));
+                    continue;
+                }
+                calls.push((id, parent, next + 1, skipped));
+                match order[v] {
+                    None => {
+                        order[v] = Some(time);
+                        low[v] = time;
+                        time += 1;
+                        if id == start {
+                            children += 1;
+                        }
+                        calls.push((v, Some(id), 0, false));
+                    }
+                    Some(o) => low[id] = low[id].min(o),
+                }
+                continue;
+            }
+
+            if let Some(p) = parent {
+                low[p] = low[p].min(low[id]);
+                let order_p = order[p].unwrap();
+                if low[id] > order_p {
+                    bridges.push((p.min(id), p.max(id)));
+                }
+                if p != start && low[id] >= order_p {
+                    is_articulation[p] = true;
+                }
+            }
+        }
+        if children >= 2 {
+            is_articulation[start] = true;
+        }
+    }
+
+    bridges.sort_unstable();
+    let points = (0..len).filter(|&id| is_articulation[id]).collect();
+    (bridges, points)
+}
+
+/// Edges of an undirected graph whose removal disconnects it, as `(u, v)` with
+/// `u < v` in ascending order.
+pub fn bridges(graph: &impl Graph) -> Vec<(usize, usize)> {
+    lowlink(graph).0
+}
+
+/// Nodes of an undirected graph whose removal disconnects it, in ascending order.
+pub fn articulation_points(graph: &impl Graph) -> Vec<usize> {
+    lowlink(graph).1
+}
+
+/// ALDS1_11_D: `n m`, `m` lines of friends `s t`, then `q` queries `s t`
+/// answered with `yes` or `no`.
+pub fn input_connected_components(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let mut read_pair = || -> Result<(usize, usize), Box<dyn Error>> {
+        let line = lines.next().ok_or("unexpected end of input")??;
+        let mut values = line.split_whitespace().map(|x| x.parse::<usize>());
+        match (values.next(), values.next()) {
+            (Some(a), Some(b)) => Ok((a?, b?)),
+            _ => Err("expected two values".into()),
+        }
+    };
+
+    let (len, m) = read_pair()?;
+    let mut graph = AdjacencyList::new(len, false);
+    for _ in 0..m {
+        let (s, t) = read_pair()?;
+        graph.add_edge(s, t, 1)?;
+    }
+    let component = connected_components(&graph);
+
+    let line = lines.next().ok_or("unexpected end of input")??;
+    let q: usize = line.trim().parse()?;
+    for line in lines.take(q) {
+        let values = line?
+            .split_whitespace()
+            .map(|x| x.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let same = component[values[0]] == component[values[1]];
+        writeln!(writer, "{}", if same { "yes" } else { "no" })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn directed(len: usize, edges: &[(usize, usize)]) -> AdjacencyList {
+        let mut graph = AdjacencyList::new(len, true);
+        for &(u, v) in edges {
+            graph.add_edge(u, v, 1).unwrap();
+        }
+        graph
+    }
+
+    fn undirected(len: usize, edges: &[(usize, usize)]) -> AdjacencyList {
+        let mut graph = AdjacencyList::new(len, false);
+        for &(u, v) in edges {
+            graph.add_edge(u, v, 1).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test1() {
+        let input = [
+            "10 9", "0 1", "0 2", "3 4", "5 7", "5 6", "6 7", "6 8", "7 8", "8 9", "3", "0 1",
+            "5 9", "1 3",
+        ]
+        .join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_connected_components(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ["yes", "yes", "no", ""].join("\n")
+        );
+    }
+
+    #[test]
+    fn test_scc() {
+        let graph = directed(
+            8,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (5, 3),
+                (6, 5),
+                (6, 7),
+                (7, 6),
+            ],
+        );
+
+        let tarjan = tarjan_scc(&graph);
+        let kosaraju = kosaraju_scc(&graph);
+        assert_eq!(relabel(&tarjan), vec![0, 0, 0, 1, 1, 1, 2, 2]);
+        assert_eq!(relabel(&kosaraju), vec![0, 0, 0, 1, 1, 1, 2, 2]);
+
+        for e in graph.edges() {
+            assert!(tarjan[e.from] >= tarjan[e.to]);
+            assert!(kosaraju[e.from] <= kosaraju[e.to]);
+        }
+    }
+
+    #[test]
+    fn test_scc_deep() {
+        let len = 100_000;
+        let edges: Vec<(usize, usize)> = (0..len).map(|i| (i, (i + 1) % len)).collect();
+        let graph = directed(len, &edges);
+        assert!(tarjan_scc(&graph).iter().all(|&c| c == 0));
+        assert!(kosaraju_scc(&graph).iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn test_bridges() {
+        let graph = undirected(
+            7,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (5, 3),
+                (5, 6),
+            ],
+        );
+        assert_eq!(bridges(&graph), vec![(2, 3), (5, 6)]);
+        assert_eq!(articulation_points(&graph), vec![2, 3, 5]);
+
+        // a doubled edge is not a bridge
+        let graph = undirected(3, &[(0, 1), (0, 1), (1, 2)]);
+        assert_eq!(bridges(&graph), vec![(1, 2)]);
+        assert_eq!(articulation_points(&graph), vec![1]);
+
+        let graph = undirected(4, &[(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(articulation_points(&graph), vec![0]);
+        assert_eq!(
+            connected_components(&undirected(4, &[(3, 1)])),
+            vec![0, 1, 2, 1]
+        );
+    }
+}
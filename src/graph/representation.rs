@@ -0,0 +1,336 @@
+use itertools::Itertools;
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{prelude::*, BufReader},
+};
+
+pub type Weight = isize;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GraphError {
+    NodeOutOfRange(usize),
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GraphError: {:?}", self)
+    }
+}
+
+impl Error for GraphError {}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub weight: Weight,
+}
+
+/// A graph over nodes `0..n`. Unweighted edges have weight 1.
+pub trait Graph {
+    fn new(len: usize, directed: bool) -> Self
+    where
+        Self: Sized;
+    fn len(&self) -> usize;
+    fn is_directed(&self) -> bool;
+    /// Adds an edge; an undirected graph gets it in both directions.
+    fn add_edge(&mut self, from: usize, to: usize, weight: Weight) -> Result<(), GraphError>;
+    /// Weight of the edge `from -> to`, if there is one.
+    fn weight(&self, from: usize, to: usize) -> Option<Weight>;
+    /// Outgoing edges of `id` as `(to, weight)`.
+    fn neighbors(&self, id: usize) -> Vec<(usize, Weight)>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every edge once; undirected edges are reported with `from <= to`.
+    fn edges(&self) -> Vec<Edge> {
+        (0..self.len())
+            .flat_map(|from| {
+                self.neighbors(from)
+                    .into_iter()
+                    .map(move |(to, weight)| Edge { from, to, weight })
+            })
+            .filter(|e| self.is_directed() || e.from <= e.to)
+            .collect()
+    }
+}
+
+fn check_range(len: usize, ids: &[usize]) -> Result<(), GraphError> {
+    match ids.iter().find(|&&x| x >= len) {
+        Some(&x) => Err(GraphError::NodeOutOfRange(x)),
+        None => Ok(()),
+    }
+}
+
+/// Copies the edges of `graph` into another representation.
+fn convert<G: Graph, H: Graph>(graph: &G) -> H {
+    let mut result = H::new(graph.len(), graph.is_directed());
+    for e in graph.edges() {
+        result.add_edge(e.from, e.to, e.weight).unwrap();
+    }
+    result
+}
+
+/// Outgoing edges of every node, in insertion order. Parallel edges are kept.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AdjacencyList {
+    directed: bool,
+    adjacency: Vec<Vec<(usize, Weight)>>,
+}
+
+impl Graph for AdjacencyList {
+    fn new(len: usize, directed: bool) -> Self {
+        AdjacencyList {
+            directed,
+            adjacency: vec![vec![]; len],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, weight: Weight) -> Result<(), GraphError> {
+        check_range(self.len(), &[from, to])?;
+        self.adjacency[from].push((to, weight));
+        if !self.directed && from != to {
+            self.adjacency[to].push((from, weight));
+        }
+        Ok(())
+    }
+
+    /// The first matching edge when there are parallel ones.
+    fn weight(&self, from: usize, to: usize) -> Option<Weight> {
+        self.adjacency[from]
+            .iter()
+            .find(|&&(v, _)| v == to)
+            .map(|&(_, w)| w)
+    }
+
+    fn neighbors(&self, id: usize) -> Vec<(usize, Weight)> {
+        self.adjacency[id].clone()
+    }
+}
+
+impl From<&AdjacencyMatrix> for AdjacencyList {
+    fn from(graph: &AdjacencyMatrix) -> Self {
+        convert(graph)
+    }
+}
+
+/// An `n x n` matrix of edge weights. Adding an existing edge replaces its weight.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AdjacencyMatrix {
+    directed: bool,
+    matrix: Vec<Vec<Option<Weight>>>,
+}
+
+impl AdjacencyMatrix {
+    /// Rows of 0 and 1, as printed for ALDS1_11_A.
+    pub fn to_bit_rows(&self) -> Vec<Vec<u8>> {
+        self.matrix
+            .iter()
+            .map(|row| row.iter().map(|w| w.is_some() as u8).collect())
+            .collect()
+    }
+}
+
+impl Graph for AdjacencyMatrix {
+    fn new(len: usize, directed: bool) -> Self {
+        AdjacencyMatrix {
+            directed,
+            matrix: vec![vec![None; len]; len],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.matrix.len()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, weight: Weight) -> Result<(), GraphError> {
+        check_range(self.len(), &[from, to])?;
+        self.matrix[from][to] = Some(weight);
+        if !self.directed {
+            self.matrix[to][from] = Some(weight);
+        }
+        Ok(())
+    }
+
+    fn weight(&self, from: usize, to: usize) -> Option<Weight> {
+        self.matrix[from][to]
+    }
+
+    /// Neighbors in ascending order.
+    fn neighbors(&self, id: usize) -> Vec<(usize, Weight)> {
+        self.matrix[id]
+            .iter()
+            .enumerate()
+            .filter_map(|(to, w)| w.map(|w| (to, w)))
+            .collect()
+    }
+}
+
+impl From<&AdjacencyList> for AdjacencyMatrix {
+    fn from(graph: &AdjacencyList) -> Self {
+        convert(graph)
+    }
+}
+
+/// Reads the ALDS1_11_A format: `n`, then for every node a line `u k v1 ... vk`
+/// with 1-based ids. The nodes are numbered from 0 in the result.
+pub fn read_adjacency_list<G: Graph>(
+    reader: &mut impl Read,
+    directed: bool,
+) -> Result<G, Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let len: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut graph = G::new(len, directed);
+
+    for line in lines.take(len) {
+        let values = line?
+            .split_whitespace()
+            .map(|x| x.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let id = |x: usize| x.checked_sub(1).ok_or("node ids start from 1");
+        let (u, k) = (id(values[0])?, values[1]);
+        for &v in &values[2..2 + k] {
+            graph.add_edge(u, id(v)?, 1)?;
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Reads the ALDS1_12_B format: `n`, then for every node a line
+/// `u k v1 c1 ... vk ck` of 0-based ids and edge weights.
+pub fn read_weighted_adjacency_list<G: Graph>(
+    reader: &mut impl Read,
+    directed: bool,
+) -> Result<G, Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let len: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut graph = G::new(len, directed);
+
+    for line in lines.take(len) {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+        let mut next = || tokens.next().ok_or("unexpected end of line");
+        let u: usize = next()?.parse()?;
+        let k: usize = next()?.parse()?;
+        for _ in 0..k {
+            let v: usize = next()?.parse()?;
+            let w: Weight = next()?.parse()?;
+            graph.add_edge(u, v, w)?;
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Reads the ALDS1_12_A format: `n`, then an `n x n` matrix of weights where -1
+/// means no edge.
+pub fn read_adjacency_matrix<G: Graph>(
+    reader: &mut impl Read,
+    directed: bool,
+) -> Result<G, Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let len: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut graph = G::new(len, directed);
+
+    for (from, line) in lines.take(len).enumerate() {
+        let values = line?
+            .split_whitespace()
+            .map(|x| x.parse::<Weight>())
+            .collect::<Result<Vec<_>, _>>()?;
+        for (to, &w) in values.iter().enumerate() {
+            // an undirected edge appears twice in the matrix
+            if w != -1 && (directed || from <= to) {
+                graph.add_edge(from, to, w)?;
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+pub fn input_adjacency_matrix(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let graph: AdjacencyMatrix = read_adjacency_list(reader, true)?;
+    for row in graph.to_bit_rows() {
+        writeln!(writer, "{}", row.into_iter().join(" "))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let input = ["4", "1 2 2 4", "2 1 4", "3 0", "4 1 3"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_adjacency_matrix(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ["0 1 0 1", "0 0 0 1", "0 0 0 0", "0 0 1 0", ""].join("\n")
+        );
+    }
+
+    #[test]
+    fn test_conversion() {
+        let mut list = AdjacencyList::new(4, false);
+        list.add_edge(0, 1, 5).unwrap();
+        list.add_edge(2, 1, 3).unwrap();
+        list.add_edge(3, 3, 1).unwrap();
+        assert_eq!(list.add_edge(0, 4, 1), Err(GraphError::NodeOutOfRange(4)));
+
+        let matrix = AdjacencyMatrix::from(&list);
+        assert_eq!(matrix.weight(1, 2), Some(3));
+        assert_eq!(matrix.neighbors(1), vec![(0, 5), (2, 3)]);
+        assert_eq!(
+            matrix.edges(),
+            vec![
+                Edge {
+                    from: 0,
+                    to: 1,
+                    weight: 5
+                },
+                Edge {
+                    from: 1,
+                    to: 2,
+                    weight: 3
+                },
+                Edge {
+                    from: 3,
+                    to: 3,
+                    weight: 1
+                },
+            ]
+        );
+
+        let back = AdjacencyList::from(&matrix);
+        assert_eq!(back.neighbors(1), vec![(0, 5), (2, 3)]);
+        assert_eq!(back.edges(), matrix.edges());
+        assert_eq!(AdjacencyMatrix::from(&back), matrix);
+    }
+}
@@ -0,0 +1,492 @@
+use super::representation::{
+    read_weighted_adjacency_list, AdjacencyList, AdjacencyMatrix, Graph, Weight,
+};
+use crate::data_structure::priority_queue::{PriorityQueue, VecHeap};
+use itertools::Itertools;
+use std::{
+    cmp::Reverse,
+    collections::VecDeque,
+    error::Error,
+    fmt::Display,
+    io::{prelude::*, BufReader},
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ShortestPathError {
+    /// A cycle of negative total weight makes some distances unbounded.
+    NegativeCycle,
+    /// An edge weight the algorithm cannot handle: negative for Dijkstra, other
+    /// than 0 or 1 for 0-1 BFS.
+    InvalidWeight(Weight),
+}
+
+impl Display for ShortestPathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ShortestPathError: {:?}", self)
+    }
+}
+
+impl Error for ShortestPathError {}
+
+/// Distances from a single source and the tree of shortest paths.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ShortestPaths {
+    /// `None` when unreachable.
+    pub distances: Vec<Option<Weight>>,
+    pub parents: Vec<Option<usize>>,
+}
+
+impl ShortestPaths {
+    fn new(len: usize, source: usize) -> Self {
+        let mut result = ShortestPaths {
+            distances: vec![None; len],
+            parents: vec![None; len],
+        };
+        result.distances[source] = Some(0);
+        result
+    }
+
+    /// Lowers the distance of `to` if going through `from` is shorter.
+    fn relax(&mut self, from: usize, to: usize, weight: Weight) -> bool {
+        let d = match self.distances[from] {
+            Some(d) => d + weight,
+            None => return false,
+        };
+        match self.distances[to] {
+            Some(cur) if cur <= d => false,
+            _ => {
+                self.distances[to] = Some(d);
+                self.parents[to] = Some(from);
+                true
+            }
+        }
+    }
+
+    /// A shortest path from the source to `target`, both included.
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        self.distances[target]?;
+        let mut path = vec![target];
+        let mut cur = target;
+        while let Some(p) = self.parents[cur] {
+            path.push(p);
+            cur = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+fn adjacency(graph: &impl Graph) -> Vec<Vec<(usize, Weight)>> {
+    (0..graph.len()).map(|id| graph.neighbors(id)).collect()
+}
+
+/// Dijkstra's algorithm in O(n^2), picking the closest unfinished node by a linear
+/// scan. Suits dense graphs such as an `AdjacencyMatrix`. Fails on a negative
+/// weight reachable from `source`.
+pub fn dijkstra_dense(
+    graph: &impl Graph,
+    source: usize,
+) -> Result<ShortestPaths, ShortestPathError> {
+    let len = graph.len();
+    let mut result = ShortestPaths::new(len, source);
+    let mut done = vec![false; len];
+
+    loop {
+        let next = (0..len)
+            .filter(|&id| !done[id])
+            .filter_map(|id| result.distances[id].map(|d| (d, id)))
+            .min();
+        let id = match next {
+            Some((_, id)) => id,
+            None => break,
+        };
+        done[id] = true;
+        for (v, w) in graph.neighbors(id) {
+            if w < 0 {
+                return Err(ShortestPathError::InvalidWeight(w));
+            }
+            if !done[v] {
+                result.relax(id, v, w);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Dijkstra's algorithm with a binary heap in O((n + m) log n). Fails on a
+/// negative weight reachable from `source`.
+pub fn dijkstra(graph: &impl Graph, source: usize) -> Result<ShortestPaths, ShortestPathError> {
+    let len = graph.len();
+    let mut result = ShortestPaths::new(len, source);
+    let mut heap = VecHeap::new(len);
+    heap.insert(Reverse((0, source)));
+
+    while let Some(Reverse((d, id))) = heap.extract() {
+        // skip entries left behind by later improvements
+        if result.distances[id] != Some(d) {
+            continue;
+        }
+        for (v, w) in graph.neighbors(id) {
+            if w < 0 {
+                return Err(ShortestPathError::InvalidWeight(w));
+            }
+            if result.relax(id, v, w) {
+                heap.insert(Reverse((d + w, v)));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Bellman-Ford in O(nm). Fails if a negative cycle is reachable from `source`.
+pub fn bellman_ford(graph: &impl Graph, source: usize) -> Result<ShortestPaths, ShortestPathError> {
+    let len = graph.len();
+    let adjacency = adjacency(graph);
+    let mut result = ShortestPaths::new(len, source);
+
+    for round in 0..len {
+        let mut updated = false;
+        for (u, edges) in adjacency.iter().enumerate() {
+            for &(v, w) in edges {
+                updated |= result.relax(u, v, w);
+            }
+        }
+        if !updated {
+            return Ok(result);
+        }
+        // shortest paths have at most n - 1 edges
+        if round == len - 1 {
+            return Err(ShortestPathError::NegativeCycle);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Shortest paths on graphs whose weights are all 0 or 1, in O(n + m) with a
+/// double-ended queue.
+pub fn zero_one_bfs(graph: &impl Graph, source: usize) -> Result<ShortestPaths, ShortestPathError> {
+    let len = graph.len();
+    let mut result = ShortestPaths::new(len, source);
+    let mut done = vec![false; len];
+    let mut deque = VecDeque::with_capacity(len);
+    deque.push_back(source);
+
+    while let Some(id) = deque.pop_front() {
+        if done[id] {
+            continue;
+        }
+        done[id] = true;
+        for (v, w) in graph.neighbors(id) {
+            if w != 0 && w != 1 {
+                return Err(ShortestPathError::InvalidWeight(w));
+            }
+            if result.relax(id, v, w) {
+                if w == 0 {
+                    deque.push_front(v);
+                } else {
+                    deque.push_back(v);
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Distances between every pair of nodes.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AllPairsShortestPaths {
+    /// `distances[i][j]` is `None` when `j` is unreachable from `i`.
+    pub distances: Vec<Vec<Option<Weight>>>,
+    /// The node after `i` on a shortest path from `i` to `j`.
+    next: Vec<Vec<Option<usize>>>,
+}
+
+impl AllPairsShortestPaths {
+    /// A shortest path from `from` to `to`, both included.
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        self.distances[from][to]?;
+        let mut path = vec![from];
+        let mut cur = from;
+        while cur != to {
+            cur = self.next[cur][to]?;
+            path.push(cur);
+        }
+        Some(path)
+    }
+}
+
+/// Floyd-Warshall in O(n^3). Fails if the graph has a negative cycle.
+pub fn floyd_warshall(graph: &impl Graph) -> Result<AllPairsShortestPaths, ShortestPathError> {
+    let len = graph.len();
+    let mut distances = vec![vec![None; len]; len];
+    let mut next = vec![vec![None; len]; len];
+    for i in 0..len {
+        distances[i][i] = Some(0);
+        next[i][i] = Some(i);
+        for (j, w) in graph.neighbors(i) {
+            if distances[i][j].is_none_or(|d| w < d) {
+                distances[i][j] = Some(w);
+                next[i][j] = Some(j);
+            }
+        }
+    }
+
+    for k in 0..len {
+        for i in 0..len {
+            let dik = match distances[i][k] {
+                Some(d) => d,
+                None => continue,
+            };
+            for j in 0..len {
+                if let Some(dkj) = distances[k][j] {
+                    if distances[i][j].is_none_or(|d| dik + dkj < d) {
+                        distances[i][j] = Some(dik + dkj);
+                        next[i][j] = next[i][k];
+                    }
+                }
+            }
+        }
+
+        // stop before a negative cycle drives the distances down to an overflow
+        if (0..len).any(|i| matches!(distances[i][i], Some(d) if d < 0)) {
+            return Err(ShortestPathError::NegativeCycle);
+        }
+    }
+
+    Ok(AllPairsShortestPaths { distances, next })
+}
+
+/// Reads `n m` (and the source when `with_source`), then `m` directed edges `s t d`.
+fn read_edge_list(
+    reader: &mut impl Read,
+    with_source: bool,
+) -> Result<(AdjacencyList, usize), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let head = lines
+        .next()
+        .ok_or("unexpected end of input")??
+        .split_whitespace()
+        .map(|x| x.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let (len, m) = (head[0], head[1]);
+    let source = if with_source { head[2] } else { 0 };
+
+    let mut graph = AdjacencyList::new(len, true);
+    for line in lines.take(m) {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+        let mut next = || tokens.next().ok_or("unexpected end of line");
+        let (s, t): (usize, usize) = (next()?.parse()?, next()?.parse()?);
+        let d: Weight = next()?.parse()?;
+        graph.add_edge(s, t, d)?;
+    }
+    Ok((graph, source))
+}
+
+fn write_distances(writer: &mut impl Write, result: &ShortestPaths) -> Result<(), Box<dyn Error>> {
+    for (id, d) in result.distances.iter().enumerate() {
+        writeln!(writer, "{} {}", id, d.ok_or("unreachable node")?)?;
+    }
+    Ok(())
+}
+
+/// ALDS1_12_B: prints `id d` for every node, the distance from node 0.
+pub fn input_dijkstra_dense(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let graph: AdjacencyMatrix = read_weighted_adjacency_list(reader, true)?;
+    write_distances(writer, &dijkstra_dense(&graph, 0)?)
+}
+
+/// ALDS1_12_C: the same as ALDS1_12_B for up to 10000 nodes.
+pub fn input_dijkstra(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let graph: AdjacencyList = read_weighted_adjacency_list(reader, true)?;
+    write_distances(writer, &dijkstra(&graph, 0)?)
+}
+
+/// GRL_1_B: prints the distance from `r` to every node or `INF`, or
+/// `NEGATIVE CYCLE`.
+pub fn input_bellman_ford(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let (graph, source) = read_edge_list(reader, true)?;
+    match bellman_ford(&graph, source) {
+        Ok(result) => {
+            for d in result.distances {
+                writeln!(writer, "{}", d.map_or("INF".to_string(), |d| d.to_string()))?;
+            }
+        }
+        Err(ShortestPathError::NegativeCycle) => writeln!(writer, "NEGATIVE CYCLE")?,
+        Err(e) => return Err(e.into()),
+    }
+    Ok(())
+}
+
+/// GRL_1_C: prints the distance matrix with `INF` for unreachable pairs, or
+/// `NEGATIVE CYCLE`.
+pub fn input_floyd_warshall(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let (graph, _) = read_edge_list(reader, false)?;
+    match floyd_warshall(&graph) {
+        Ok(result) => {
+            for row in result.distances {
+                let row = row
+                    .into_iter()
+                    .map(|d| d.map_or("INF".to_string(), |d| d.to_string()))
+                    .join(" ");
+                writeln!(writer, "{}", row)?;
+            }
+        }
+        Err(ShortestPathError::NegativeCycle) => writeln!(writer, "NEGATIVE CYCLE")?,
+        Err(e) => return Err(e.into()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! run {
+        ($f:ident, $input:expr) => {{
+            let input = $input.join("\n");
+            let mut output: Vec<u8> = vec![];
+            assert!($f(&mut input.as_bytes(), &mut output).is_ok());
+            String::from_utf8(output).unwrap()
+        }};
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let input = [
+            "5",
+            "0 3 2 3 3 1 1 2",
+            "1 2 0 2 3 4",
+            "2 3 0 3 3 1 4 1",
+            "3 4 2 1 0 1 1 4 4 3",
+            "4 2 2 1 3 3",
+        ];
+        let expected = ["0 0", "1 2", "2 2", "3 1", "4 3", ""].join("\n");
+        assert_eq!(run!(input_dijkstra_dense, &input), expected);
+        assert_eq!(run!(input_dijkstra, &input), expected);
+    }
+
+    #[test]
+    fn test_bellman_ford() {
+        let edges = ["0 1 2", "0 2 3", "1 2 -5", "1 3 1", "2 3 2"];
+        let input: Vec<&str> = std::iter::once("4 5 0")
+            .chain(edges.iter().copied())
+            .collect();
+        assert_eq!(
+            run!(input_bellman_ford, &input),
+            ["0", "2", "-3", "-1", ""].join("\n")
+        );
+
+        let input: Vec<&str> = std::iter::once("4 5 1")
+            .chain(edges.iter().copied())
+            .collect();
+        assert_eq!(
+            run!(input_bellman_ford, &input),
+            ["INF", "0", "-5", "-3", ""].join("\n")
+        );
+
+        let input: Vec<&str> = std::iter::once("4 6 0")
+            .chain(edges.iter().copied())
+            .chain(std::iter::once("3 1 0"))
+            .collect();
+        assert_eq!(run!(input_bellman_ford, &input), "NEGATIVE CYCLE\n");
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let input = ["4 6", "0 1 1", "0 2 5", "1 2 2", "1 3 4", "2 3 1", "3 2 7"];
+        assert_eq!(
+            run!(input_floyd_warshall, &input),
+            ["0 1 3 4", "INF 0 2 3", "INF INF 0 1", "INF INF 7 0", ""].join("\n")
+        );
+
+        let input = ["4 6", "0 1 1", "0 2 5", "1 2 2", "1 3 4", "2 3 1", "3 2 -7"];
+        assert_eq!(run!(input_floyd_warshall, &input), "NEGATIVE CYCLE\n");
+
+        // heavy negative edges overflow unless the search stops at the first negative cycle
+        let len = 100;
+        let edges = (0..len)
+            .cartesian_product(0..len)
+            .filter(|(u, v)| u != v)
+            .map(|(u, v)| format!("{} {} -10000000", u, v));
+        let input: Vec<String> = std::iter::once(format!("{} {}", len, len * (len - 1)))
+            .chain(edges)
+            .collect();
+        assert_eq!(run!(input_floyd_warshall, &input), "NEGATIVE CYCLE\n");
+    }
+
+    #[test]
+    fn test_paths() {
+        let mut graph = AdjacencyList::new(5, false);
+        for &(u, v, w) in &[
+            (0, 1, 1),
+            (1, 2, 0),
+            (0, 2, 1),
+            (2, 3, 1),
+            (3, 4, 0),
+            (1, 4, 1),
+        ] {
+            graph.add_edge(u, v, w).unwrap();
+        }
+
+        let expected = dijkstra(&graph, 0).unwrap();
+        assert_eq!(
+            expected.distances,
+            vec![Some(0), Some(1), Some(1), Some(2), Some(2)]
+        );
+        assert_eq!(
+            dijkstra_dense(&graph, 0).unwrap().distances,
+            expected.distances
+        );
+        assert_eq!(
+            bellman_ford(&graph, 0).unwrap().distances,
+            expected.distances
+        );
+        assert_eq!(
+            zero_one_bfs(&graph, 0).unwrap().distances,
+            expected.distances
+        );
+        assert_eq!(expected.path_to(4), Some(vec![0, 1, 4]));
+
+        let all = floyd_warshall(&graph).unwrap();
+        assert_eq!(all.distances[0], expected.distances);
+        assert_eq!(all.path(0, 3), Some(vec![0, 2, 3]));
+        assert_eq!(all.path(4, 4), Some(vec![4]));
+
+        graph.add_edge(0, 4, 2).unwrap();
+        assert_eq!(
+            zero_one_bfs(&graph, 0),
+            Err(ShortestPathError::InvalidWeight(2))
+        );
+        graph.add_edge(3, 1, -1).unwrap();
+        assert_eq!(
+            dijkstra(&graph, 0),
+            Err(ShortestPathError::InvalidWeight(-1))
+        );
+        assert_eq!(
+            dijkstra_dense(&graph, 0),
+            Err(ShortestPathError::InvalidWeight(-1))
+        );
+
+        let unreachable = AdjacencyList::new(2, true);
+        assert_eq!(dijkstra(&unreachable, 0).unwrap().path_to(1), None);
+        assert_eq!(floyd_warshall(&unreachable).unwrap().path(0, 1), None);
+    }
+}
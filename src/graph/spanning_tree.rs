@@ -0,0 +1,190 @@
+use super::representation::{read_adjacency_matrix, AdjacencyMatrix, Edge, Graph, Weight};
+use crate::data_structure::{
+    priority_queue::{PriorityQueue, VecHeap},
+    union_find::UnionFind,
+};
+use std::{cmp::Reverse, error::Error, io::prelude::*};
+
+/// A minimum spanning tree of every connected component.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SpanningForest {
+    pub weight: Weight,
+    /// Chosen edges as `from <= to`.
+    pub edges: Vec<Edge>,
+}
+
+impl SpanningForest {
+    fn new() -> Self {
+        SpanningForest {
+            weight: 0,
+            edges: vec![],
+        }
+    }
+
+    fn push(&mut self, from: usize, to: usize, weight: Weight) {
+        self.weight += weight;
+        self.edges.push(Edge {
+            from: from.min(to),
+            to: from.max(to),
+            weight,
+        });
+    }
+
+    /// Number of trees in the forest of a graph with `len` nodes.
+    pub fn trees(&self, len: usize) -> usize {
+        len - self.edges.len()
+    }
+}
+
+/// Prim's algorithm in O(n^2) for an undirected graph, growing a tree from every
+/// node not yet reached. Suits dense graphs such as an `AdjacencyMatrix`.
+pub fn prim_dense(graph: &impl Graph) -> SpanningForest {
+    let len = graph.len();
+    let mut result = SpanningForest::new();
+    let mut done = vec![false; len];
+    // cheapest known edge into every node as (weight, from)
+    let mut best: Vec<Option<(Weight, usize)>> = vec![None; len];
+
+    for start in 0..len {
+        if done[start] {
+            continue;
+        }
+        let mut next = Some(start);
+        while let Some(id) = next {
+            done[id] = true;
+            if let Some((w, from)) = best[id] {
+                result.push(from, id, w);
+            }
+            for (v, w) in graph.neighbors(id) {
+                if !done[v] && best[v].is_none_or(|(b, _)| w < b) {
+                    best[v] = Some((w, id));
+                }
+            }
+            next = (0..len)
+                .filter(|&v| !done[v])
+                .filter_map(|v| best[v].map(|(w, _)| (w, v)))
+                .min()
+                .map(|(_, v)| v);
+        }
+    }
+
+    result
+}
+
+/// Prim's algorithm with a binary heap in O(m log n) for an undirected graph.
+pub fn prim(graph: &impl Graph) -> SpanningForest {
+    let len = graph.len();
+    let mut result = SpanningForest::new();
+    let mut done = vec![false; len];
+    let mut heap = VecHeap::new(len);
+
+    for start in 0..len {
+        if done[start] {
+            continue;
+        }
+        done[start] = true;
+        for (v, w) in graph.neighbors(start) {
+            heap.insert(Reverse((w, v, start)));
+        }
+        while let Some(Reverse((w, id, from))) = heap.extract() {
+            if done[id] {
+                continue;
+            }
+            done[id] = true;
+            result.push(from, id, w);
+            for (v, w) in graph.neighbors(id) {
+                if !done[v] {
+                    heap.insert(Reverse((w, v, id)));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Kruskal's algorithm in O(m log m), taking the edges by ascending weight and
+/// skipping those that would close a cycle. Edge directions are ignored.
+pub fn kruskal(graph: &impl Graph) -> SpanningForest {
+    let mut edges = graph.edges();
+    edges.sort_by_key(|e| e.weight);
+
+    let mut result = SpanningForest::new();
+    let mut uf = UnionFind::new(graph.len());
+    for e in edges {
+        if uf.union(e.from, e.to) {
+            result.push(e.from, e.to, e.weight);
+        }
+    }
+    result
+}
+
+/// ALDS1_12_A: prints the total weight of a minimum spanning tree.
+pub fn input_prim(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let graph: AdjacencyMatrix = read_adjacency_matrix(reader, false)?;
+    writeln!(writer, "{}", prim_dense(&graph).weight)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::representation::AdjacencyList;
+
+    #[test]
+    fn test1() {
+        let input = [
+            "5",
+            " -1 2 3 1 -1",
+            " 2 -1 -1 4 -1",
+            " 3 -1 -1 1 1",
+            " 1 4 1 -1 3",
+            " -1 -1 1 3 -1",
+        ]
+        .join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_prim(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "5\n");
+    }
+
+    #[test]
+    fn test_forest() {
+        let mut graph = AdjacencyList::new(7, false);
+        for &(u, v, w) in &[
+            (0, 1, 4),
+            (0, 2, 1),
+            (1, 2, 2),
+            (1, 3, 5),
+            (2, 3, 8),
+            (4, 5, 3),
+            (5, 6, 1),
+            (4, 6, 2),
+        ] {
+            graph.add_edge(u, v, w).unwrap();
+        }
+
+        let expected = kruskal(&graph);
+        assert_eq!(expected.weight, 11);
+        assert_eq!(expected.trees(graph.len()), 2);
+
+        let sorted = |mut forest: SpanningForest| {
+            forest.edges.sort_by_key(|e| (e.from, e.to));
+            forest
+        };
+        let expected = sorted(expected);
+        assert_eq!(
+            expected
+                .edges
+                .iter()
+                .map(|e| (e.from, e.to))
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (1, 2), (1, 3), (4, 6), (5, 6)]
+        );
+        assert_eq!(sorted(prim(&graph)), expected);
+        assert_eq!(sorted(prim_dense(&graph)), expected);
+        assert_eq!(sorted(prim_dense(&AdjacencyMatrix::from(&graph))), expected);
+    }
+}
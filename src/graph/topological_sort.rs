@@ -0,0 +1,357 @@
+use super::{
+    representation::{AdjacencyList, Graph, Weight},
+    traversal::neighbor_ids,
+};
+use crate::data_structure::queue::{Queue, VecQueue};
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{prelude::*, BufReader},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TopologicalSortError {
+    /// Nodes `v0, v1, ..., vk` with edges `v0 -> v1 -> ... -> vk -> v0`.
+    Cycle(Vec<usize>),
+    /// A count does not fit in its integer type.
+    Overflow,
+    NodeOutOfRange(usize),
+}
+
+impl Display for TopologicalSortError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TopologicalSortError: {:?}", self)
+    }
+}
+
+impl Error for TopologicalSortError {}
+
+/// Kahn's algorithm: repeatedly takes a node with no remaining incoming edges.
+/// Ready nodes wait in a FIFO queue, starting in ascending order.
+pub fn kahn(graph: &impl Graph) -> Result<Vec<usize>, TopologicalSortError> {
+    let len = graph.len();
+    let neighbors = neighbor_ids(graph);
+    let mut in_degree = vec![0; len];
+    for &v in neighbors.iter().flatten() {
+        in_degree[v] += 1;
+    }
+
+    let mut queue = VecQueue::new(len);
+    for id in (0..len).filter(|&id| in_degree[id] == 0) {
+        queue.enqueue(id);
+    }
+    let mut order = Vec::with_capacity(len);
+    while !queue.is_empty() {
+        let id = queue.dequeue();
+        order.push(id);
+        for &v in &neighbors[id] {
+            in_degree[v] -= 1;
+            if in_degree[v] == 0 {
+                queue.enqueue(v);
+            }
+        }
+    }
+
+    if order.len() < len {
+        // the leftover nodes contain a cycle
+        return dfs_topological_sort(graph);
+    }
+    Ok(order)
+}
+
+/// Topological sort by reversed DFS finishing order, walked with an explicit
+/// stack. Reports the first cycle met through a back edge.
+pub fn dfs_topological_sort(graph: &impl Graph) -> Result<Vec<usize>, TopologicalSortError> {
+    #[derive(Copy, Clone, PartialEq)]
+    enum State {
+        New,
+        Active,
+        Finished,
+    }
+
+    let len = graph.len();
+    let neighbors = neighbor_ids(graph);
+    let mut state = vec![State::New; len];
+    let mut parents = vec![None; len];
+    let mut order = Vec::with_capacity(len);
+
+    for start in 0..len {
+        if state[start] != State::New {
+            continue;
+        }
+        state[start] = State::Active;
+        // (node, index of the next neighbor to look at)
+        let mut stack = vec![(start, 0)];
+        while let Some((id, next)) = stack.pop() {
+            let v = match neighbors[id].get(next) {
+                Some(&v) => v,
+                None => {
+                    state[id] = State::Finished;
+                    order.push(id);
+                    continue;
+                }
+            };
+            stack.push((id, next + 1));
+            match state[v] {
+                State::New => {
+                    state[v] = State::Active;
+                    parents[v] = Some(id);
+                    stack.push((v, 0));
+                }
+                State::Active => {
+                    // v is an ancestor of id on the stack
+                    let mut cycle = vec![id];
+                    let mut cur = id;
+                    while cur != v {
+                        cur = parents[cur].unwrap();
+                        cycle.push(cur);
+                    }
+                    cycle.reverse();
+                    return Err(TopologicalSortError::Cycle(cycle));
+                }
+                State::Finished => {}
+            }
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
+
+/// A heaviest path of a DAG.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct LongestPath {
+    pub weight: Weight,
+    /// Empty only for a graph without nodes.
+    pub path: Vec<usize>,
+}
+
+/// The path of largest total weight, starting and ending anywhere.
+pub fn longest_path(graph: &impl Graph) -> Result<LongestPath, TopologicalSortError> {
+    let order = kahn(graph)?;
+    let len = graph.len();
+    // heaviest path ending at every node; 0 for the path of the node alone
+    let mut best = vec![0; len];
+    let mut parents = vec![None; len];
+    for &id in &order {
+        for (v, w) in graph.neighbors(id) {
+            if best[id] + w > best[v] {
+                best[v] = best[id] + w;
+                parents[v] = Some(id);
+            }
+        }
+    }
+
+    let end = match (0..len).max_by_key(|&id| best[id]) {
+        Some(end) => end,
+        None => {
+            return Ok(LongestPath {
+                weight: 0,
+                path: vec![],
+            })
+        }
+    };
+    let mut path = vec![end];
+    let mut cur = end;
+    while let Some(p) = parents[cur] {
+        path.push(p);
+        cur = p;
+    }
+    path.reverse();
+    Ok(LongestPath {
+        weight: best[end],
+        path,
+    })
+}
+
+/// Number of distinct paths from `from` to `to`; a node has one path to itself.
+/// The count grows exponentially with the depth of the DAG; a count that does
+/// not fit in a `u128` is reported as `TopologicalSortError::Overflow`.
+pub fn count_paths(
+    graph: &impl Graph,
+    from: usize,
+    to: usize,
+) -> Result<u128, TopologicalSortError> {
+    if let Some(&x) = [from, to].iter().find(|&&x| x >= graph.len()) {
+        return Err(TopologicalSortError::NodeOutOfRange(x));
+    }
+    let order = kahn(graph)?;
+    // None once a count has overflowed, which only matters if it reaches `to`
+    let mut count = vec![Some(0u128); graph.len()];
+    count[from] = Some(1);
+    for &id in &order {
+        if count[id] == Some(0) {
+            continue;
+        }
+        for (v, _) in graph.neighbors(id) {
+            count[v] = count[v].zip(count[id]).and_then(|(a, b)| a.checked_add(b));
+        }
+    }
+    count[to].ok_or(TopologicalSortError::Overflow)
+}
+
+/// Reads `n m` and `m` directed edges `s t`.
+fn read_edges(reader: &mut impl Read) -> Result<AdjacencyList, Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader
+        .lines()
+        .map(|line| -> Result<Vec<usize>, Box<dyn Error>> {
+            Ok(line?
+                .split_whitespace()
+                .map(|x| x.parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()?)
+        });
+    let head = lines.next().ok_or("unexpected end of input")??;
+    let mut graph = AdjacencyList::new(head[0], true);
+    for line in lines.take(head[1]) {
+        let values = line?;
+        graph.add_edge(values[0], values[1], 1)?;
+    }
+    Ok(graph)
+}
+
+/// GRL_4_A: prints 1 if the directed graph has a cycle, 0 otherwise.
+pub fn input_cycle_detection(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let graph = read_edges(reader)?;
+    let has_cycle = dfs_topological_sort(&graph).is_err();
+    writeln!(writer, "{}", if has_cycle { 1 } else { 0 })?;
+    Ok(())
+}
+
+/// GRL_4_B: prints the nodes in topological order, one per line.
+pub fn input_topological_sort(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let graph = read_edges(reader)?;
+    for id in kahn(&graph)? {
+        writeln!(writer, "{}", id)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn directed(len: usize, edges: &[(usize, usize, Weight)]) -> AdjacencyList {
+        let mut graph = AdjacencyList::new(len, true);
+        for &(u, v, w) in edges {
+            graph.add_edge(u, v, w).unwrap();
+        }
+        graph
+    }
+
+    fn assert_topological(graph: &AdjacencyList, order: &[usize]) {
+        let mut position = vec![0; graph.len()];
+        for (i, &id) in order.iter().enumerate() {
+            position[id] = i;
+        }
+        assert_eq!(order.len(), graph.len());
+        for e in graph.edges() {
+            assert!(position[e.from] < position[e.to]);
+        }
+    }
+
+    #[test]
+    fn test1() {
+        let input = ["6 6", "0 1", "1 2", "3 1", "3 4", "4 5", "5 2"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_topological_sort(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ["0", "3", "1", "4", "5", "2", ""].join("\n")
+        );
+
+        for (input, expected) in &[
+            (["3 3", "0 1", "0 2", "1 2"].join("\n"), "0\n"),
+            (["3 3", "0 1", "1 2", "2 0"].join("\n"), "1\n"),
+        ] {
+            let mut output: Vec<u8> = vec![];
+            let result = input_cycle_detection(&mut input.as_bytes(), &mut output);
+            assert!(result.is_ok());
+            assert_eq!(String::from_utf8(output).unwrap(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_cycle() {
+        let graph = directed(6, &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 1, 1), (4, 5, 1)]);
+        let cycle = TopologicalSortError::Cycle(vec![1, 2, 3]);
+        assert_eq!(dfs_topological_sort(&graph), Err(cycle.clone()));
+        assert_eq!(kahn(&graph), Err(cycle.clone()));
+        assert_eq!(longest_path(&graph).map(|p| p.weight), Err(cycle));
+
+        let graph = directed(2, &[(1, 1, 1)]);
+        assert_eq!(kahn(&graph), Err(TopologicalSortError::Cycle(vec![1])));
+    }
+
+    #[test]
+    fn test_dag() {
+        let graph = directed(
+            6,
+            &[
+                (0, 1, 5),
+                (0, 2, 3),
+                (1, 3, 6),
+                (1, 2, 2),
+                (2, 4, 4),
+                (2, 5, 2),
+                (2, 3, 7),
+                (3, 5, 1),
+                (3, 4, -1),
+                (4, 5, -2),
+            ],
+        );
+        assert_topological(&graph, &kahn(&graph).unwrap());
+        assert_topological(&graph, &dfs_topological_sort(&graph).unwrap());
+
+        assert_eq!(
+            longest_path(&graph),
+            Ok(LongestPath {
+                weight: 15,
+                path: vec![0, 1, 2, 3, 5]
+            })
+        );
+        assert_eq!(count_paths(&graph, 0, 5), Ok(10));
+        assert_eq!(count_paths(&graph, 3, 3), Ok(1));
+        assert_eq!(count_paths(&graph, 5, 0), Ok(0));
+        assert_eq!(
+            count_paths(&graph, 6, 0),
+            Err(TopologicalSortError::NodeOutOfRange(6))
+        );
+        assert_eq!(
+            count_paths(&graph, 0, 7),
+            Err(TopologicalSortError::NodeOutOfRange(7))
+        );
+
+        // 2^100 paths through a chain of diamonds
+        let mut edges = vec![];
+        for i in 0..100 {
+            let base = 3 * i;
+            edges.extend(&[(base, base + 1, 1), (base, base + 2, 1)]);
+            edges.extend(&[(base + 1, base + 3, 1), (base + 2, base + 3, 1)]);
+        }
+        let graph = directed(301, &edges);
+        assert_eq!(count_paths(&graph, 0, 300), Ok(1 << 100));
+
+        // 2^128 paths do not fit
+        for i in 100..128 {
+            let base = 3 * i;
+            edges.extend(&[(base, base + 1, 1), (base, base + 2, 1)]);
+            edges.extend(&[(base + 1, base + 3, 1), (base + 2, base + 3, 1)]);
+        }
+        let graph = directed(385, &edges);
+        assert_eq!(count_paths(&graph, 0, 381), Ok(1 << 127));
+        assert_eq!(
+            count_paths(&graph, 0, 384),
+            Err(TopologicalSortError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use super::representation::{read_adjacency_list, AdjacencyList, Graph};
+use crate::data_structure::{
+    queue::{Queue, VecQueue},
+    stack::{Stack, VecStack},
+};
+use std::{error::Error, io::prelude::*};
+
+/// Result of a depth-first search over the whole graph.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Dfs {
+    /// Time at which each node was first reached, starting from 1.
+    pub discovery: Vec<usize>,
+    /// Time at which each node's neighbors were all explored.
+    pub finish: Vec<usize>,
+    pub parents: Vec<Option<usize>>,
+}
+
+impl Dfs {
+    /// Nodes in the order they finished.
+    pub fn finish_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.finish.len()).collect();
+        order.sort_by_key(|&id| self.finish[id]);
+        order
+    }
+}
+
+/// Neighbor ids of every node, dropping the weights.
+pub(crate) fn neighbor_ids(graph: &impl Graph) -> Vec<Vec<usize>> {
+    (0..graph.len())
+        .map(|id| graph.neighbors(id).into_iter().map(|(to, _)| to).collect())
+        .collect()
+}
+
+/// Depth-first search with an explicit stack, starting from every unvisited node
+/// in ascending order and following the neighbors in the graph's order.
+pub fn dfs(graph: &impl Graph) -> Dfs {
+    let len = graph.len();
+    let neighbors = neighbor_ids(graph);
+    let mut result = Dfs {
+        discovery: vec![0; len],
+        finish: vec![0; len],
+        parents: vec![None; len],
+    };
+    let mut time = 0;
+    // (node, index of the next neighbor to look at); every node is pushed at most once
+    let mut stack: VecStack<(usize, usize)> = VecStack::new(len);
+
+    for start in 0..len {
+        if result.discovery[start] != 0 {
+            continue;
+        }
+        time += 1;
+        result.discovery[start] = time;
+        stack.push((start, 0)).unwrap();
+
+        while let Ok((id, next)) = stack.pop() {
+            let found = neighbors[id][next..]
+                .iter()
+                .position(|&v| result.discovery[v] == 0);
+            match found {
+                Some(i) => {
+                    let v = neighbors[id][next + i];
+                    stack.push((id, next + i + 1)).unwrap();
+                    time += 1;
+                    result.discovery[v] = time;
+                    result.parents[v] = Some(id);
+                    stack.push((v, 0)).unwrap();
+                }
+                None => {
+                    time += 1;
+                    result.finish[id] = time;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Result of a breadth-first search from a single source.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Bfs {
+    /// Number of edges from the source; `None` when unreachable.
+    pub distances: Vec<Option<usize>>,
+    pub parents: Vec<Option<usize>>,
+}
+
+impl Bfs {
+    /// A shortest path from the source to `target`, both included.
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        self.distances[target]?;
+        let mut path = vec![target];
+        let mut cur = target;
+        while let Some(p) = self.parents[cur] {
+            path.push(p);
+            cur = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+pub fn bfs(graph: &impl Graph, source: usize) -> Bfs {
+    let len = graph.len();
+    let mut result = Bfs {
+        distances: vec![None; len],
+        parents: vec![None; len],
+    };
+    let mut queue = VecQueue::new(len);
+    result.distances[source] = Some(0);
+    queue.enqueue(source);
+
+    while !queue.is_empty() {
+        let id = queue.dequeue();
+        let d = result.distances[id].map(|d| d + 1);
+        for (v, _) in graph.neighbors(id) {
+            if result.distances[v].is_none() {
+                result.distances[v] = d;
+                result.parents[v] = Some(id);
+                queue.enqueue(v);
+            }
+        }
+    }
+
+    result
+}
+
+pub type Cell = (usize, usize);
+
+/// Result of a breadth-first search on a grid, indexed by `[row][column]`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct GridBfs {
+    pub distances: Vec<Vec<Option<usize>>>,
+    pub parents: Vec<Vec<Option<Cell>>>,
+}
+
+impl GridBfs {
+    /// A shortest path from the start to `target`, both included.
+    pub fn path_to(&self, target: Cell) -> Option<Vec<Cell>> {
+        self.distances[target.0][target.1]?;
+        let mut path = vec![target];
+        let mut cur = target;
+        while let Some(p) = self.parents[cur.0][cur.1] {
+            path.push(p);
+            cur = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search through the 4-neighborhood over the passable (`true`)
+/// cells. The grid is given by its rows; the start cell is always entered.
+pub fn bfs_grid(grid: &[Vec<bool>], start: Cell) -> GridBfs {
+    let height = grid.len();
+    let width = grid.first().map_or(0, |row| row.len());
+    let mut result = GridBfs {
+        distances: vec![vec![None; width]; height],
+        parents: vec![vec![None; width]; height],
+    };
+    let mut queue = VecQueue::new(height * width);
+    result.distances[start.0][start.1] = Some(0);
+    queue.enqueue(start);
+
+    while !queue.is_empty() {
+        let (r, c) = queue.dequeue();
+        let d = result.distances[r][c].map(|d| d + 1);
+        let candidates = [
+            (r.wrapping_sub(1), c),
+            (r + 1, c),
+            (r, c.wrapping_sub(1)),
+            (r, c + 1),
+        ];
+        for &(nr, nc) in &candidates {
+            if nr < height && nc < width && grid[nr][nc] && result.distances[nr][nc].is_none() {
+                result.distances[nr][nc] = d;
+                result.parents[nr][nc] = Some((r, c));
+                queue.enqueue((nr, nc));
+            }
+        }
+    }
+
+    result
+}
+
+/// ALDS1_11_B: prints `id d f` for every node.
+pub fn input_dfs(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let graph: AdjacencyList = read_adjacency_list(reader, true)?;
+    let result = dfs(&graph);
+    for id in 0..graph.len() {
+        writeln!(
+            writer,
+            "{} {} {}",
+            id + 1,
+            result.discovery[id],
+            result.finish[id]
+        )?;
+    }
+    Ok(())
+}
+
+/// ALDS1_11_C: prints `id d` for every node, the distance from node 1 or -1.
+pub fn input_bfs(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let graph: AdjacencyList = read_adjacency_list(reader, true)?;
+    if graph.is_empty() {
+        return Ok(());
+    }
+    let result = bfs(&graph, 0);
+    for (id, d) in result.distances.into_iter().enumerate() {
+        writeln!(writer, "{} {}", id + 1, d.map_or(-1, |d| d as isize))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dfs() {
+        let input = ["4", "1 1 2", "2 1 4", "3 0", "4 1 3"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_dfs(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ["1 1 8", "2 2 7", "3 4 5", "4 3 6", ""].join("\n")
+        );
+
+        let input = ["6", "1 2 2 3", "2 2 3 4", "3 1 5", "4 1 6", "5 1 6", "6 0"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_dfs(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ["1 1 12", "2 2 11", "3 3 8", "4 9 10", "5 4 7", "6 5 6", ""].join("\n")
+        );
+    }
+
+    #[test]
+    fn test_bfs() {
+        let input = ["4", "1 2 2 4", "2 1 4", "3 0", "4 1 3"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_bfs(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ["1 0", "2 1", "3 2", "4 1", ""].join("\n")
+        );
+    }
+
+    #[test]
+    fn test_path() {
+        let mut graph = AdjacencyList::new(6, false);
+        for &(u, v) in &[(0, 1), (1, 2), (2, 3), (0, 4), (4, 3)] {
+            graph.add_edge(u, v, 1).unwrap();
+        }
+
+        let result = bfs(&graph, 0);
+        assert_eq!(result.path_to(3), Some(vec![0, 4, 3]));
+        assert_eq!(result.path_to(0), Some(vec![0]));
+        assert_eq!(result.path_to(5), None);
+
+        let result = dfs(&graph);
+        assert_eq!(
+            result.parents,
+            vec![None, Some(0), Some(1), Some(2), Some(3), None]
+        );
+        assert_eq!(result.finish_order(), vec![4, 3, 2, 1, 0, 5]);
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid: Vec<Vec<bool>> = ["..#.", ".##.", "....", "#..."]
+            .iter()
+            .map(|row| row.chars().map(|c| c == '.').collect())
+            .collect();
+
+        let result = bfs_grid(&grid, (0, 0));
+        assert_eq!(result.distances[0][3], Some(7));
+        assert_eq!(result.distances[0][2], None);
+        assert_eq!(result.distances[3][0], None);
+        assert_eq!(
+            result.path_to((1, 3)),
+            Some(vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (2, 3), (1, 3)])
+        );
+    }
+
+    #[test]
+    fn test_deep_dfs() {
+        let len = 100_000;
+        let mut graph = AdjacencyList::new(len, true);
+        for i in 0..len - 1 {
+            graph.add_edge(i, i + 1, 1).unwrap();
+        }
+        let result = dfs(&graph);
+        assert_eq!(result.finish[0], 2 * len);
+        assert_eq!(result.discovery[len - 1], len);
+    }
+}
@@ -0,0 +1 @@
+pub mod sliding_puzzle;
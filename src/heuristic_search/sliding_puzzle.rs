@@ -0,0 +1,579 @@
+use crate::data_structure::{
+    priority_queue::{PriorityQueue, VecHeap},
+    queue::{Queue, VecQueue},
+};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    io::{prelude::*, BufReader},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum PuzzleError {
+    /// The tiles are not `0..width * width` in some order.
+    InvalidTiles(Vec<u8>),
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PuzzleError: {:?}", self)
+    }
+}
+
+impl Error for PuzzleError {}
+
+/// A move of the blank, which swaps it with the tile on that side.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Move {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+const MOVES: [Move; 4] = [Move::Up, Move::Down, Move::Left, Move::Right];
+
+impl Move {
+    pub fn opposite(self) -> Self {
+        match self {
+            Move::Up => Move::Down,
+            Move::Down => Move::Up,
+            Move::Left => Move::Right,
+            Move::Right => Move::Left,
+        }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Move::Up => 'U',
+            Move::Down => 'D',
+            Move::Left => 'L',
+            Move::Right => 'R',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// Largest width, as the tiles are `u8`.
+const MAX_WIDTH: usize = 16;
+
+/// A `width x width` sliding puzzle in row-major order with 0 as the blank.
+/// The goal is `1, 2, ..., width * width - 1` followed by the blank.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Puzzle {
+    width: usize,
+    tiles: Vec<u8>,
+}
+
+impl Puzzle {
+    pub fn new(width: usize, tiles: Vec<u8>) -> Result<Self, PuzzleError> {
+        let len = tiles.len();
+        let mut seen = vec![false; len];
+        let valid = width > 0
+            && len == width * width
+            && len <= 256
+            && tiles
+                .iter()
+                .all(|&t| (t as usize) < len && !std::mem::replace(&mut seen[t as usize], true));
+        if !valid {
+            return Err(PuzzleError::InvalidTiles(tiles));
+        }
+        Ok(Puzzle { width, tiles })
+    }
+
+    pub fn goal(width: usize) -> Self {
+        let len = width * width;
+        let tiles = (1..len).chain(Some(0)).map(|t| t as u8).collect();
+        Puzzle { width, tiles }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn tiles(&self) -> &[u8] {
+        &self.tiles
+    }
+
+    pub fn is_goal(&self) -> bool {
+        self.tiles[self.tiles.len() - 1] == 0
+            && self.tiles[..self.tiles.len() - 1]
+                .iter()
+                .enumerate()
+                .all(|(i, &t)| t as usize == i + 1)
+    }
+
+    fn blank(&self) -> usize {
+        self.tiles.iter().position(|&t| t == 0).unwrap()
+    }
+
+    /// Index of the cell where tile `t` belongs.
+    fn goal_position(&self, t: u8) -> usize {
+        if t == 0 {
+            self.tiles.len() - 1
+        } else {
+            t as usize - 1
+        }
+    }
+
+    /// Whether the goal can be reached. Every move keeps the parity of the
+    /// inversions among the tiles on an odd width, and flips it with every row
+    /// change of the blank on an even width.
+    pub fn is_solvable(&self) -> bool {
+        let tiles: Vec<u8> = self.tiles.iter().copied().filter(|&t| t != 0).collect();
+        let mut inversions = 0;
+        for (i, a) in tiles.iter().enumerate() {
+            inversions += tiles[i + 1..].iter().filter(|&b| b < a).count();
+        }
+        if self.width.is_multiple_of(2) {
+            inversions += self.width - 1 - self.blank() / self.width;
+        }
+        inversions.is_multiple_of(2)
+    }
+
+    /// The cell next to `cell` in the direction of `m`, if it is on the board.
+    fn step(&self, cell: usize, m: Move) -> Option<usize> {
+        let (row, col) = (cell / self.width, cell % self.width);
+        match m {
+            Move::Up if row > 0 => Some(cell - self.width),
+            Move::Down if row + 1 < self.width => Some(cell + self.width),
+            Move::Left if col > 0 => Some(cell - 1),
+            Move::Right if col + 1 < self.width => Some(cell + 1),
+            _ => None,
+        }
+    }
+
+    /// Moves the blank from `blank` in place and returns its new position, or
+    /// `None` if it would leave the board.
+    fn slide(&mut self, blank: usize, m: Move) -> Option<usize> {
+        let target = self.step(blank, m)?;
+        self.tiles.swap(blank, target);
+        Some(target)
+    }
+
+    /// The puzzle after moving the blank, or `None` if it would leave the board.
+    pub fn apply(&self, m: Move) -> Option<Self> {
+        let mut next = self.clone();
+        next.slide(self.blank(), m)?;
+        Some(next)
+    }
+
+    /// Number of moves between cells `a` and `b` on an empty board.
+    fn distance(&self, a: usize, b: usize) -> usize {
+        let w = self.width;
+        let rows = (a / w).max(b / w) - (a / w).min(b / w);
+        let cols = (a % w).max(b % w) - (a % w).min(b % w);
+        rows + cols
+    }
+
+    /// Sum of the distances of every tile to its goal cell.
+    pub fn manhattan(&self) -> usize {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|&(_, &t)| t != 0)
+            .map(|(i, &t)| self.distance(i, self.goal_position(t)))
+            .sum()
+    }
+
+    /// Extra moves on top of `manhattan`: tiles in their goal row (or column)
+    /// but in the wrong order there have to leave the line and come back. At
+    /// least all but a longest correctly ordered subsequence must do so.
+    pub fn linear_conflict(&self) -> usize {
+        (0..self.width)
+            .map(|line| self.line_conflict(line, true) + self.line_conflict(line, false))
+            .sum()
+    }
+
+    /// The linear conflict of row `line`, or of column `line` if not `row`,
+    /// without allocating.
+    fn line_conflict(&self, line: usize, row: bool) -> usize {
+        let w = self.width;
+        // positions along the line where its own tiles belong, in their order
+        let mut goals = [0; MAX_WIDTH];
+        let mut len = 0;
+        for k in 0..w {
+            let t = self.tiles[if row { line * w + k } else { k * w + line }];
+            if t == 0 {
+                continue;
+            }
+            let goal = self.goal_position(t);
+            let (goal_line, offset) = if row {
+                (goal / w, goal % w)
+            } else {
+                (goal % w, goal / w)
+            };
+            if goal_line == line {
+                goals[len] = offset;
+                len += 1;
+            }
+        }
+
+        // longest increasing subsequence in O(len^2); ending[i] is the longest
+        // one ending at goals[i]
+        let mut ending = [0; MAX_WIDTH];
+        for i in 0..len {
+            ending[i] = 1
+                + (0..i)
+                    .filter(|&j| goals[j] < goals[i])
+                    .map(|j| ending[j])
+                    .max()
+                    .unwrap_or(0);
+        }
+        let longest = ending[..len].iter().copied().max().unwrap_or(0);
+        2 * (len - longest)
+    }
+
+    /// Linear conflict of the lines through cells `a` and `b`, which are
+    /// next to each other.
+    fn local_conflict(&self, a: usize, b: usize) -> usize {
+        let w = self.width;
+        if a / w == b / w {
+            self.line_conflict(a / w, true)
+                + self.line_conflict(a % w, false)
+                + self.line_conflict(b % w, false)
+        } else {
+            self.line_conflict(a / w, true)
+                + self.line_conflict(b / w, true)
+                + self.line_conflict(a % w, false)
+        }
+    }
+
+    /// Moves the blank like `slide` and updates `estimate`, the `heuristic`
+    /// before the move, from the one tile that moves and the lines it touches.
+    /// Returns the new blank position and estimate.
+    fn slide_with_estimate(
+        &mut self,
+        blank: usize,
+        m: Move,
+        estimate: usize,
+    ) -> Option<(usize, usize)> {
+        let target = self.step(blank, m)?;
+        let goal = self.goal_position(self.tiles[target]);
+        let before = self.distance(target, goal) + self.local_conflict(blank, target);
+        self.tiles.swap(blank, target);
+        let after = self.distance(blank, goal) + self.local_conflict(blank, target);
+        Some((target, estimate + after - before))
+    }
+
+    /// Admissible estimate of the moves left.
+    pub fn heuristic(&self) -> usize {
+        self.manhattan() + self.linear_conflict()
+    }
+}
+
+/// Follows `parents` back from `end` to the start state.
+fn unwind(parents: &[Option<(usize, Move)>], end: usize) -> Vec<Move> {
+    let mut moves = vec![];
+    let mut cur = end;
+    while let Some((parent, m)) = parents[cur] {
+        moves.push(m);
+        cur = parent;
+    }
+    moves.reverse();
+    moves
+}
+
+/// Shortest move sequence by breadth-first search over every reachable state.
+/// Suits the 8-puzzle, whose 9!/2 states fit in memory.
+pub fn bfs(start: &Puzzle) -> Option<Vec<Move>> {
+    if !start.is_solvable() {
+        return None;
+    }
+
+    let mut ids = HashMap::new();
+    let mut parents = vec![None];
+    ids.insert(start.clone(), 0);
+    let mut queue = VecQueue::new(1024);
+    queue.enqueue((start.clone(), 0));
+    while !queue.is_empty() {
+        let (puzzle, id) = queue.dequeue();
+        if puzzle.is_goal() {
+            return Some(unwind(&parents, id));
+        }
+        for &m in &MOVES {
+            if let Some(next) = puzzle.apply(m) {
+                if ids.contains_key(&next) {
+                    continue;
+                }
+                let next_id = parents.len();
+                parents.push(Some((id, m)));
+                ids.insert(next.clone(), next_id);
+                queue.enqueue((next, next_id));
+            }
+        }
+    }
+    None
+}
+
+/// Shortest move sequence by A* search guided by `Puzzle::heuristic`. States
+/// are reopened when a shorter way to them turns up.
+pub fn a_star(start: &Puzzle) -> Option<Vec<Move>> {
+    if !start.is_solvable() {
+        return None;
+    }
+
+    let mut ids = HashMap::new();
+    let mut states = vec![start.clone()];
+    let mut costs = vec![0];
+    let mut estimates = vec![start.heuristic()];
+    let mut parents = vec![None];
+    ids.insert(start.clone(), 0);
+    let mut heap = VecHeap::new(1024);
+    heap.insert(Reverse((estimates[0], estimates[0], 0)));
+    while let Some(Reverse((f, _, id))) = heap.extract() {
+        if f > costs[id] + estimates[id] {
+            // a shorter way was found since
+            continue;
+        }
+        if states[id].is_goal() {
+            return Some(unwind(&parents, id));
+        }
+        let g = costs[id] + 1;
+        for &m in &MOVES {
+            let next = match states[id].apply(m) {
+                Some(next) => next,
+                None => continue,
+            };
+            let next_id = match ids.get(&next) {
+                Some(&next_id) if costs[next_id] <= g => continue,
+                Some(&next_id) => {
+                    costs[next_id] = g;
+                    parents[next_id] = Some((id, m));
+                    next_id
+                }
+                None => {
+                    let next_id = states.len();
+                    estimates.push(next.heuristic());
+                    costs.push(g);
+                    parents.push(Some((id, m)));
+                    ids.insert(next.clone(), next_id);
+                    states.push(next);
+                    next_id
+                }
+            };
+            let h = estimates[next_id];
+            heap.insert(Reverse((g + h, h, next_id)));
+        }
+    }
+    None
+}
+
+/// Shortest move sequence by iterative deepening A*: depth-first searches cut
+/// off where `Puzzle::heuristic` exceeds a bound, raised each round to the
+/// smallest estimate that was cut. Needs memory only for the current path.
+pub fn ida_star(start: &Puzzle) -> Option<Vec<Move>> {
+    fn search(
+        puzzle: &mut Puzzle,
+        blank: usize,
+        estimate: usize,
+        bound: usize,
+        path: &mut Vec<Move>,
+        next_bound: &mut usize,
+    ) -> bool {
+        let f = path.len() + estimate;
+        if f > bound {
+            *next_bound = (*next_bound).min(f);
+            return false;
+        }
+        if puzzle.is_goal() {
+            return true;
+        }
+
+        for &m in &MOVES {
+            if path.last() == Some(&m.opposite()) {
+                continue;
+            }
+            let (moved, next_estimate) = match puzzle.slide_with_estimate(blank, m, estimate) {
+                Some(next) => next,
+                None => continue,
+            };
+            path.push(m);
+            if search(puzzle, moved, next_estimate, bound, path, next_bound) {
+                return true;
+            }
+            path.pop();
+            puzzle.slide(moved, m.opposite());
+        }
+        false
+    }
+
+    if !start.is_solvable() {
+        return None;
+    }
+
+    let mut puzzle = start.clone();
+    let blank = puzzle.blank();
+    let estimate = puzzle.heuristic();
+    let mut bound = estimate;
+    let mut path = vec![];
+    loop {
+        let mut next_bound = usize::MAX;
+        if search(
+            &mut puzzle,
+            blank,
+            estimate,
+            bound,
+            &mut path,
+            &mut next_bound,
+        ) {
+            return Some(path);
+        }
+        bound = next_bound;
+    }
+}
+
+/// Reads a `width x width` board, one row per line.
+fn read_puzzle(reader: &mut impl Read, width: usize) -> Result<Puzzle, Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut tiles = Vec::with_capacity(width * width);
+    for line in reader.lines().take(width) {
+        for x in line?.split_whitespace() {
+            tiles.push(x.parse::<u8>()?);
+        }
+    }
+    Ok(Puzzle::new(width, tiles)?)
+}
+
+/// ALDS1_13_B: prints the fewest moves solving an 8-puzzle.
+pub fn input_8_puzzle(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let puzzle = read_puzzle(reader, 3)?;
+    let moves = bfs(&puzzle).ok_or("unsolvable puzzle")?;
+    writeln!(writer, "{}", moves.len())?;
+    Ok(())
+}
+
+/// ALDS1_13_C: prints the fewest moves solving a 15-puzzle.
+pub fn input_15_puzzle(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let puzzle = read_puzzle(reader, 4)?;
+    let moves = ida_star(&puzzle).ok_or("unsolvable puzzle")?;
+    writeln!(writer, "{}", moves.len())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::XorShift;
+    use itertools::Itertools;
+
+    #[test]
+    fn test1() {
+        let input = ["1 3 0", "4 2 5", "7 8 6"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_8_puzzle(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "4\n");
+    }
+
+    #[test]
+    fn test2() {
+        let input = ["1 2 3 4", "6 7 8 0", "5 10 11 12", "9 13 14 15"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_15_puzzle(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "8\n");
+    }
+
+    fn solve_with(puzzle: &Puzzle, moves: &[Move]) -> Puzzle {
+        moves
+            .iter()
+            .fold(puzzle.clone(), |p, &m| p.apply(m).unwrap())
+    }
+
+    /// A solvable puzzle `steps` random moves away from the goal.
+    fn scrambled(width: usize, steps: usize, rng: &mut XorShift) -> Puzzle {
+        let mut puzzle = Puzzle::goal(width);
+        for _ in 0..steps {
+            if let Some(next) = puzzle.apply(MOVES[rng.below(4)]) {
+                puzzle = next;
+            }
+        }
+        puzzle
+    }
+
+    #[test]
+    fn test_solvers() {
+        let mut rng = XorShift::new(1);
+        for _ in 0..10 {
+            let puzzle = scrambled(3, 60, &mut rng);
+            assert!(puzzle.is_solvable());
+            let expected = bfs(&puzzle).unwrap();
+            assert!(solve_with(&puzzle, &expected).is_goal());
+            assert!(puzzle.heuristic() <= expected.len());
+            for moves in &[a_star(&puzzle).unwrap(), ida_star(&puzzle).unwrap()] {
+                assert_eq!(moves.len(), expected.len());
+                assert!(solve_with(&puzzle, moves).is_goal());
+            }
+        }
+        for _ in 0..5 {
+            let puzzle = scrambled(4, 40, &mut rng);
+            let expected = ida_star(&puzzle).unwrap();
+            assert!(solve_with(&puzzle, &expected).is_goal());
+            assert!(puzzle.heuristic() <= expected.len());
+            assert_eq!(a_star(&puzzle).unwrap().len(), expected.len());
+        }
+
+        let puzzle = Puzzle::new(3, vec![1, 2, 3, 4, 5, 6, 7, 0, 8]).unwrap();
+        let moves = ida_star(&puzzle).unwrap();
+        assert_eq!(moves.iter().join(""), "R");
+        assert_eq!(bfs(&Puzzle::goal(3)), Some(vec![]));
+    }
+
+    #[test]
+    fn test_unsolvable() {
+        // two tiles swapped
+        let puzzle = Puzzle::new(3, vec![2, 1, 3, 4, 5, 6, 7, 8, 0]).unwrap();
+        assert!(!puzzle.is_solvable());
+        assert_eq!(bfs(&puzzle), None);
+        assert_eq!(a_star(&puzzle), None);
+        assert_eq!(ida_star(&puzzle), None);
+
+        let tiles = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 15, 14, 0];
+        assert!(!Puzzle::new(4, tiles).unwrap().is_solvable());
+        let tiles = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 0, 13, 14, 15, 12];
+        assert!(Puzzle::new(4, tiles).unwrap().is_solvable());
+
+        assert!(Puzzle::new(2, vec![0, 1, 1, 3]).is_err());
+        assert!(Puzzle::new(2, vec![0, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_heuristic() {
+        // 3 2 1 in the top row: 2 + 0 + 2 away, and two of them have to leave the row
+        let puzzle = Puzzle::new(3, vec![3, 2, 1, 4, 5, 6, 7, 8, 0]).unwrap();
+        assert_eq!(puzzle.manhattan(), 4);
+        assert_eq!(puzzle.linear_conflict(), 4);
+        assert_eq!(Puzzle::goal(4).heuristic(), 0);
+
+        // the incremental estimate follows a random walk
+        let mut rng = XorShift::new(3);
+        for &width in &[3, 4, 5] {
+            let mut puzzle = Puzzle::goal(width);
+            let mut blank = puzzle.blank();
+            let mut estimate = 0;
+            for _ in 0..500 {
+                let m = MOVES[rng.below(4)];
+                if let Some(next) = puzzle.slide_with_estimate(blank, m, estimate) {
+                    blank = next.0;
+                    estimate = next.1;
+                    assert_eq!(estimate, puzzle.heuristic());
+                }
+            }
+        }
+    }
+}
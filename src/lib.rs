@@ -1,5 +1,12 @@
 pub mod data_structure;
+pub mod dp;
+pub mod geometry;
+pub mod graph;
+pub mod heuristic_search;
 pub mod recursive;
 pub mod search;
 pub mod sort;
 pub mod sort2;
+
+#[cfg(test)]
+mod test_util;
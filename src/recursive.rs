@@ -1,2 +1,7 @@
+pub mod backtracking;
 pub mod exhaustive_search;
 pub mod koch_curve;
+pub mod l_system;
+pub mod n_queens;
+pub mod render;
+pub mod sudoku;
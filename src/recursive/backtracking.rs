@@ -0,0 +1,205 @@
+/// A problem solved by extending a partial state one choice at a time and
+/// undoing the choice once everything below it has been explored.
+pub trait Backtrack {
+    type Choice;
+    type Solution;
+
+    /// Whether the state is a solution.
+    fn is_complete(&self) -> bool;
+
+    /// Ways to extend an incomplete state; empty at a dead end.
+    fn choices(&self) -> Vec<Self::Choice>;
+
+    fn choose(&mut self, choice: &Self::Choice);
+
+    /// Reverts `choose` with the same choice.
+    fn unchoose(&mut self, choice: &Self::Choice);
+
+    fn solution(&self) -> Self::Solution;
+
+    /// Pruning hook checked on entering a state; returning false skips it and
+    /// everything below it.
+    fn is_promising(&self) -> bool {
+        true
+    }
+
+    /// Pruning hook checked before taking `choice`.
+    fn is_feasible(&self, _choice: &Self::Choice) -> bool {
+        true
+    }
+}
+
+/// Calls `visit` on every solution reachable from the current state until it
+/// returns false. Returns false if the walk was stopped.
+fn explore<P: Backtrack>(problem: &mut P, visit: &mut impl FnMut(&P) -> bool) -> bool {
+    if !problem.is_promising() {
+        return true;
+    }
+    if problem.is_complete() {
+        return visit(problem);
+    }
+
+    for choice in problem.choices() {
+        if !problem.is_feasible(&choice) {
+            continue;
+        }
+        problem.choose(&choice);
+        let go_on = explore(problem, visit);
+        problem.unchoose(&choice);
+        if !go_on {
+            return false;
+        }
+    }
+    true
+}
+
+/// Number of solutions.
+pub fn count(problem: &mut impl Backtrack) -> usize {
+    let mut count = 0;
+    explore(problem, &mut |_| {
+        count += 1;
+        true
+    });
+    count
+}
+
+/// The first solution in the order of `choices`.
+pub fn first<P: Backtrack>(problem: &mut P) -> Option<P::Solution> {
+    let mut result = None;
+    explore(problem, &mut |p: &P| {
+        result = Some(p.solution());
+        false
+    });
+    result
+}
+
+/// Every solution in the order of `choices`.
+pub fn all<P: Backtrack>(problem: &mut P) -> Vec<P::Solution> {
+    let mut result = vec![];
+    explore(problem, &mut |p: &P| {
+        result.push(p.solution());
+        true
+    });
+    result
+}
+
+/// Orderings of `0..n`, generated in lexicographic order.
+#[derive(Debug, Clone)]
+pub struct Permutations {
+    chosen: Vec<usize>,
+    used: Vec<bool>,
+}
+
+impl Permutations {
+    pub fn new(n: usize) -> Self {
+        Permutations {
+            chosen: Vec::with_capacity(n),
+            used: vec![false; n],
+        }
+    }
+}
+
+impl Backtrack for Permutations {
+    type Choice = usize;
+    type Solution = Vec<usize>;
+
+    fn is_complete(&self) -> bool {
+        self.chosen.len() == self.used.len()
+    }
+
+    fn choices(&self) -> Vec<usize> {
+        (0..self.used.len()).filter(|&i| !self.used[i]).collect()
+    }
+
+    fn choose(&mut self, &i: &usize) {
+        self.used[i] = true;
+        self.chosen.push(i);
+    }
+
+    fn unchoose(&mut self, &i: &usize) {
+        self.chosen.pop();
+        self.used[i] = false;
+    }
+
+    fn solution(&self) -> Vec<usize> {
+        self.chosen.clone()
+    }
+}
+
+/// Ascending `k`-element subsets of `0..n`, generated in lexicographic order.
+#[derive(Debug, Clone)]
+pub struct Combinations {
+    n: usize,
+    k: usize,
+    chosen: Vec<usize>,
+}
+
+impl Combinations {
+    pub fn new(n: usize, k: usize) -> Self {
+        Combinations {
+            n,
+            k,
+            chosen: Vec::with_capacity(k),
+        }
+    }
+}
+
+impl Backtrack for Combinations {
+    type Choice = usize;
+    type Solution = Vec<usize>;
+
+    fn is_complete(&self) -> bool {
+        self.chosen.len() == self.k
+    }
+
+    fn choices(&self) -> Vec<usize> {
+        let start = self.chosen.last().map_or(0, |&i| i + 1);
+        (start..self.n).collect()
+    }
+
+    /// Leaves enough elements after `i` to fill the subset.
+    fn is_feasible(&self, &i: &usize) -> bool {
+        i + (self.k - self.chosen.len()) <= self.n
+    }
+
+    fn choose(&mut self, &i: &usize) {
+        self.chosen.push(i);
+    }
+
+    fn unchoose(&mut self, _: &usize) {
+        self.chosen.pop();
+    }
+
+    fn solution(&self) -> Vec<usize> {
+        self.chosen.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_permutations() {
+        for n in 0..6 {
+            let expected = (0..n).permutations(n).collect::<Vec<_>>();
+            assert_eq!(all(&mut Permutations::new(n)), expected);
+            assert_eq!(count(&mut Permutations::new(n)), expected.len());
+        }
+        assert_eq!(first(&mut Permutations::new(3)), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_combinations() {
+        for n in 0..7 {
+            for k in 0..=n + 1 {
+                let expected = (0..n).combinations(k).collect::<Vec<_>>();
+                assert_eq!(all(&mut Combinations::new(n, k)), expected);
+                assert_eq!(count(&mut Combinations::new(n, k)), expected.len());
+            }
+        }
+        assert_eq!(first(&mut Combinations::new(5, 3)), Some(vec![0, 1, 2]));
+        assert_eq!(first(&mut Combinations::new(2, 3)), None);
+    }
+}
@@ -4,27 +4,158 @@ use std::{
     io::{prelude::*, BufReader},
 };
 
-pub fn compute(seq: &[usize], targets: &[usize]) -> Result<Vec<bool>, Box<dyn Error>> {
-    let combinations: Vec<Vec<Vec<&usize>>> = (0..seq.len())
-        .map(|i| seq.iter().combinations(i + 1).into_iter().collect())
-        .collect();
-    let compute_inner = |target| {
-        for i in 0..(seq.len()) {
-            for c in combinations.get(i).unwrap() {
-                if target == c.iter().map(|x| *x).sum::<usize>() {
-                    return true;
+/// Finds a subset of `seq` summing to `target` by include/exclude recursion.
+///
+/// Elements are tried from largest to smallest and a branch is cut as soon as the
+/// remaining elements cannot reach the target. The witness keeps the order of `seq`;
+/// the empty subset sums to 0.
+pub fn solve(seq: &[usize], target: usize) -> Option<Vec<usize>> {
+    fn search(
+        seq: &[usize],
+        order: &[usize],
+        rest: &[usize],
+        need: usize,
+        chosen: &mut Vec<usize>,
+    ) -> bool {
+        if need == 0 {
+            return true;
+        }
+        if order.is_empty() || rest[0] < need {
+            return false;
+        }
+
+        let x = seq[order[0]];
+        if x <= need {
+            chosen.push(order[0]);
+            if search(seq, &order[1..], &rest[1..], need - x, chosen) {
+                return true;
+            }
+            chosen.pop();
+        }
+        search(seq, &order[1..], &rest[1..], need, chosen)
+    }
+
+    let mut order: Vec<usize> = (0..seq.len()).collect();
+    order.sort_by(|&a, &b| seq[b].cmp(&seq[a]));
+
+    // rest[i] is the sum of the elements from order[i] on
+    let mut rest = vec![0usize; seq.len() + 1];
+    for i in (0..seq.len()).rev() {
+        rest[i] = rest[i + 1].saturating_add(seq[order[i]]);
+    }
+
+    let mut chosen = vec![];
+    if !search(seq, &order, &rest, target, &mut chosen) {
+        return None;
+    }
+    chosen.sort_unstable();
+    Some(chosen.into_iter().map(|i| seq[i]).collect())
+}
+
+/// Finds a subset of `seq` summing to `target` with a bitset DP in
+/// O(n * target / 64) time and memory.
+pub fn solve_dp(seq: &[usize], target: usize) -> Option<Vec<usize>> {
+    const BITS: usize = 64;
+    let words = target / BITS + 1;
+    let contains = |set: &[u64], x: usize| set[x / BITS] >> (x % BITS) & 1 == 1;
+
+    // reachable[i] is the set of sums reachable with the first i elements
+    let mut reachable: Vec<Vec<u64>> = Vec::with_capacity(seq.len() + 1);
+    let mut first = vec![0u64; words];
+    first[0] = 1;
+    reachable.push(first);
+
+    for &x in seq {
+        let prev = reachable.last().unwrap();
+        let mut next = prev.clone();
+        if x <= target {
+            let (word_shift, bit_shift) = (x / BITS, x % BITS);
+            for i in (word_shift..words).rev() {
+                let mut shifted = prev[i - word_shift] << bit_shift;
+                if bit_shift > 0 && i > word_shift {
+                    shifted |= prev[i - word_shift - 1] >> (BITS - bit_shift);
                 }
+                next[i] |= shifted;
             }
         }
-        return false;
-    };
+        reachable.push(next);
+    }
 
-    let mut result = Vec::with_capacity(targets.len());
-    for target in targets {
-        result.push(compute_inner(*target));
+    if !contains(&reachable[seq.len()], target) {
+        return None;
     }
 
-    Ok(result)
+    let mut result = vec![];
+    let mut need = target;
+    for i in (0..seq.len()).rev() {
+        if !contains(&reachable[i], need) {
+            result.push(seq[i]);
+            need -= seq[i];
+        }
+    }
+    result.reverse();
+    Some(result)
+}
+
+/// Finds a subset of `seq` summing to `target` in O(2^(n/2) * n) by splitting the
+/// sequence in half and matching the subset sums of both halves. Practical up to n = 40.
+///
+/// # Panics
+///
+/// Panics if `seq` has more than 64 elements, as each half's subsets are kept as
+/// `u64` masks.
+pub fn solve_meet_in_the_middle(seq: &[usize], target: usize) -> Option<Vec<usize>> {
+    assert!(
+        seq.len() <= 64,
+        "meet in the middle supports up to 64 elements"
+    );
+
+    fn subset_sums(seq: &[usize], target: usize) -> Vec<(usize, u64)> {
+        let mut sums = vec![(0usize, 0u64)];
+        for (i, &x) in seq.iter().enumerate() {
+            for j in 0..sums.len() {
+                let (sum, mask) = sums[j];
+                if let Some(sum) = sum.checked_add(x).filter(|&sum| sum <= target) {
+                    sums.push((sum, mask | 1 << i));
+                }
+            }
+        }
+        sums
+    }
+
+    let (left, right) = seq.split_at(seq.len() / 2);
+    let left_sums = subset_sums(left, target);
+    let mut right_sums = subset_sums(right, target);
+    right_sums.sort_unstable();
+
+    for (sum, left_mask) in left_sums {
+        let need = target - sum;
+        if let Ok(i) = right_sums.binary_search_by_key(&need, |&(sum, _)| sum) {
+            let right_mask = right_sums[i].1;
+            let result = left
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| left_mask >> i & 1 == 1)
+                .chain(
+                    right
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| right_mask >> i & 1 == 1),
+                )
+                .map(|(_, &x)| x)
+                .collect();
+            return Some(result);
+        }
+    }
+
+    None
+}
+
+pub fn compute(seq: &[usize], targets: &[usize]) -> Result<Vec<bool>, Box<dyn Error>> {
+    Ok(targets
+        .iter()
+        .map(|&target| solve(seq, target).is_some())
+        .collect())
 }
 
 pub fn input_exhaustive_search(
@@ -61,6 +192,7 @@ pub fn input_exhaustive_search(
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test_util::XorShift;
 
     #[test]
     fn test1() {
@@ -75,4 +207,62 @@ mod test {
             ["no", "no", "yes", "yes"].join("\n")
         );
     }
+
+    fn brute_force(seq: &[usize], target: usize) -> bool {
+        (0..(1u64 << seq.len())).any(|mask| {
+            seq.iter()
+                .enumerate()
+                .filter(|(i, _)| mask >> i & 1 == 1)
+                .map(|(_, x)| x)
+                .sum::<usize>()
+                == target
+        })
+    }
+
+    fn is_witness(seq: &[usize], target: usize, witness: &[usize]) -> bool {
+        let mut rest = seq.to_vec();
+        witness.iter().sum::<usize>() == target
+            && witness
+                .iter()
+                .all(|x| match rest.iter().position(|y| y == x) {
+                    Some(i) => {
+                        rest.remove(i);
+                        true
+                    }
+                    None => false,
+                })
+    }
+
+    #[test]
+    fn test_solvers() {
+        let mut rng = XorShift::new(12345);
+
+        for _ in 0..30 {
+            let len = rng.below(12);
+            let seq: Vec<usize> = (0..len).map(|_| rng.below(50) + 1).collect();
+            for target in 0..=150 {
+                let expected = brute_force(&seq, target);
+                for solver in &[solve, solve_dp, solve_meet_in_the_middle] {
+                    match solver(&seq, target) {
+                        Some(witness) => assert!(is_witness(&seq, target, &witness)),
+                        None => assert!(!expected, "{:?} {}", seq, target),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_input() {
+        let seq: Vec<usize> = (0..40).map(|i| 1_000_000_007 * (i % 7 + 1) + i).collect();
+        let target: usize = seq.iter().step_by(3).sum();
+
+        let witness = solve_meet_in_the_middle(&seq, target).unwrap();
+        assert!(is_witness(&seq, target, &witness));
+        assert_eq!(solve_meet_in_the_middle(&seq, 1), None);
+
+        let witness = solve_dp(&[3, 34, 4, 12, 5, 2], 9).unwrap();
+        assert_eq!(witness, vec![4, 5]);
+        assert_eq!(solve_dp(&[3, 34, 4, 12, 5, 2], 30), None);
+    }
 }
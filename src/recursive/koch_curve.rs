@@ -15,90 +15,57 @@ impl<T> Point<T>
 where
     T: PartialEq + Clone + Debug,
 {
-    fn new(x: T, y: T) -> Self {
+    pub fn new(x: T, y: T) -> Self {
         Point { x, y }
     }
 }
 
-fn compute(start: &PointF, end: &PointF, depth: usize) -> Vec<PointF> {
-    fn compute_left(start: &PointF, end: &PointF) -> PointF {
-        Point::new(
-            start.x + ((end.x - start.x) / 3.),
-            start.y + ((end.y - start.y) / 3.),
-        )
-    }
+/// Rotates `v` counterclockwise by `angle` radians.
+fn rotate(v: &PointF, angle: f64) -> PointF {
+    let (sin, cos) = angle.sin_cos();
+    Point::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
 
-    fn compute_right(start: &PointF, end: &PointF) -> PointF {
-        Point::new(
-            start.x + ((end.x - start.x) / 3. * 2.),
-            start.y + ((end.y - start.y) / 3. * 2.),
-        )
-    }
+/// Returns the Koch curve from `start` to `end`, including both end points.
+///
+/// The bumps are raised on the left-hand side of the direction of travel.
+pub fn compute(start: &PointF, end: &PointF, depth: usize) -> Vec<PointF> {
+    fn compute_inner(v: &mut Vec<PointF>, start: &PointF, end: &PointF, depth: usize) {
+        if depth == 0 {
+            v.push(*end);
+            return;
+        }
 
-    fn compute_vertex(left: &PointF, right: &PointF) -> PointF {
-        let length = right.x - left.x;
-        Point::new(
-            (left.x + right.x) / 2.,
-            (length.powi(2) - (length / 2.).powi(2)).sqrt(),
-        )
-    }
+        let third = Point::new((end.x - start.x) / 3., (end.y - start.y) / 3.);
+        let left = Point::new(start.x + third.x, start.y + third.y);
+        let right = Point::new(start.x + third.x * 2., start.y + third.y * 2.);
+        let apex = rotate(&third, f64::consts::FRAC_PI_3);
+        let vertex = Point::new(left.x + apex.x, left.y + apex.y);
 
-    fn compute_inner(
-        v: &mut Vec<PointF>,
-        left: &PointF,
-        right: &PointF,
-        vertex: &PointF,
-        depth: usize,
-    ) {
-        v.push(*left);
-        if depth > 0 {
-            let inner_left = compute_left(left, vertex);
-            let inner_right = compute_right(left, vertex);
-            let inner_vertex = compute_vertex(left, vertex);
-            compute_inner(v, &inner_left, &inner_right, &inner_vertex, depth - 1);
-        }
-        v.push(*vertex);
-        if depth > 0 {
-            let inner_left = compute_left(right, vertex);
-            let inner_right = compute_right(right, vertex);
-            let inner_vertex = compute_vertex(right, vertex);
-            compute_inner(v, &inner_left, &inner_right, &inner_vertex, depth - 1);
-        }
-        v.push(*right);
-
-        // let inner_start = Point::new(
-        //     left.x + ((right.x - left.x) / 3.),
-        //     left.y + ((right.y - left.y) / 3.),
-        // );
-        // let inner_end = Point::new(
-        //     left.x + ((right.x - left.x) / 3. * 2.),
-        //     left.y + ((right.y - left.y) / 3. * 2.),
-        // );
-
-        // if depth > 0 {
-        //     v.push(*left);
-        //     compute_inner(v, &inner_start, &inner_end, depth - 1);
-        //     v.push(*right);
-        // } else {
-        //     v.push(
-
-        //     )
-        //     let distance = inner_end.x - inner_start.x;
-        // }
+        compute_inner(v, start, &left, depth - 1);
+        compute_inner(v, &left, &vertex, depth - 1);
+        compute_inner(v, &vertex, &right, depth - 1);
+        compute_inner(v, &right, end, depth - 1);
     }
 
-    let mut v: Vec<PointF> = vec![];
-
+    let mut v: Vec<PointF> = Vec::with_capacity(4usize.pow(depth as u32) + 1);
     v.push(*start);
+    compute_inner(&mut v, start, end, depth);
 
-    let left = compute_left(start, end);
-
-    let right = compute_right(start, end);
+    v
+}
 
-    let vertex = compute_vertex(&left, &right);
+/// Returns the closed Koch snowflake built on the equilateral triangle whose first
+/// side runs from `start` to `end`. The last point equals the first one.
+pub fn snowflake(start: &PointF, end: &PointF, depth: usize) -> Vec<PointF> {
+    // walk the triangle clockwise so that the bumps point outwards
+    let side = Point::new(end.x - start.x, end.y - start.y);
+    let apex = rotate(&side, -f64::consts::FRAC_PI_3);
+    let third = Point::new(start.x + apex.x, start.y + apex.y);
 
-    compute_inner(&mut v, &left, &right, &vertex, depth - 1);
-    v.push(*end);
+    let mut v = compute(start, end, depth);
+    v.extend(compute(end, &third, depth).into_iter().skip(1));
+    v.extend(compute(&third, start, depth).into_iter().skip(1));
 
     v
 }
@@ -109,7 +76,7 @@ pub fn input_koch_curve(
 ) -> Result<(), Box<dyn Error>> {
     let mut s = String::new();
     reader.read_to_string(&mut s)?;
-    let n: usize = s.parse()?;
+    let n: usize = s.trim().parse()?;
 
     let start = Point::new(0.00000, 0.00000);
     let end = Point::new(100.00000, 0.00000);
@@ -145,4 +112,79 @@ mod test {
             .join("\n")
         )
     }
+
+    fn assert_points(actual: &[PointF], expected: &[(f64, f64)]) {
+        assert_eq!(actual.len(), expected.len());
+        for (p, &(x, y)) in actual.iter().zip(expected) {
+            assert!(
+                (p.x - x).abs() < 1e-5 && (p.y - y).abs() < 1e-5,
+                "{:?} {:?}",
+                p,
+                (x, y)
+            );
+        }
+    }
+
+    #[test]
+    fn test_depth() {
+        let start = Point::new(0., 0.);
+        let end = Point::new(100., 0.);
+
+        assert_points(&compute(&start, &end, 0), &[(0., 0.), (100., 0.)]);
+        assert_points(
+            &compute(&start, &end, 2),
+            &[
+                (0., 0.),
+                (11.11111, 0.),
+                (16.66667, 9.62250),
+                (22.22222, 0.),
+                (33.33333, 0.),
+                (38.88889, 9.62250),
+                (33.33333, 19.24501),
+                (44.44444, 19.24501),
+                (50., 28.86751),
+                (55.55556, 19.24501),
+                (66.66667, 19.24501),
+                (61.11111, 9.62250),
+                (66.66667, 0.),
+                (77.77778, 0.),
+                (83.33333, 9.62250),
+                (88.88889, 0.),
+                (100., 0.),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_orientation() {
+        // a vertical segment gives the horizontal curve rotated by 90 degrees
+        let horizontal = compute(&Point::new(0., 0.), &Point::new(100., 0.), 3);
+        let vertical = compute(&Point::new(0., 0.), &Point::new(0., 100.), 3);
+        let expected: Vec<(f64, f64)> = horizontal.iter().map(|p| (-p.y, p.x)).collect();
+
+        assert_eq!(vertical.len(), 4usize.pow(3) + 1);
+        assert_points(&vertical, &expected);
+    }
+
+    #[test]
+    fn test_snowflake() {
+        let start = Point::new(0., 0.);
+        let end = Point::new(90., 0.);
+        let v = snowflake(&start, &end, 2);
+
+        assert_eq!(v.len(), 3 * 4usize.pow(2) + 1);
+        assert_points(&v[..1], &[(0., 0.)]);
+        assert_points(&v[v.len() - 1..], &[(0., 0.)]);
+
+        // the bump of the first side points away from the triangle below it
+        assert!(v.iter().any(|p| p.y > 0.));
+        let area = v
+            .windows(2)
+            .map(|w| w[0].x * w[1].y - w[1].x * w[0].y)
+            .sum::<f64>()
+            .abs()
+            / 2.;
+        let triangle = 90. * 90. * 3f64.sqrt() / 4.;
+        assert!((area - triangle * (1. + 1. / 3. + 4. / 27.)).abs() < 1e-6);
+    }
 }
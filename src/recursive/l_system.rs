@@ -0,0 +1,182 @@
+use super::koch_curve::Point;
+use std::collections::HashMap;
+
+/// A deterministic L-system drawn with turtle graphics.
+///
+/// The turtle understands the symbols in `draw` (step forward drawing a line),
+/// `+` (turn left by `angle`), `-` (turn right by `angle`) and `|` (turn around).
+/// Every other symbol only takes part in the rewriting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LSystem {
+    pub axiom: String,
+    pub rules: HashMap<char, String>,
+    /// Turn angle in degrees.
+    pub angle: f64,
+    pub draw: String,
+}
+
+impl LSystem {
+    pub fn new(axiom: &str, rules: &[(char, &str)], angle: f64, draw: &str) -> Self {
+        Self {
+            axiom: axiom.into(),
+            rules: rules.iter().map(|&(c, s)| (c, s.into())).collect(),
+            angle,
+            draw: draw.into(),
+        }
+    }
+
+    pub fn koch_curve() -> Self {
+        Self::new("F", &[('F', "F+F--F+F")], 60., "F")
+    }
+
+    pub fn sierpinski_triangle() -> Self {
+        Self::new("F-G-G", &[('F', "F-G+F+G-F"), ('G', "GG")], 120., "FG")
+    }
+
+    pub fn dragon_curve() -> Self {
+        Self::new("FX", &[('X', "X+YF+"), ('Y', "-FX-Y")], 90., "F")
+    }
+
+    pub fn hilbert_curve() -> Self {
+        Self::new("A", &[('A', "-BF+AFA+FB-"), ('B', "+AF-BFB-FA+")], 90., "F")
+    }
+
+    pub fn levy_c_curve() -> Self {
+        Self::new("F", &[('F', "+F--F+")], 45., "F")
+    }
+
+    /// Applies the production rules `depth` times to the axiom.
+    pub fn expand(&self, depth: usize) -> String {
+        let mut s = self.axiom.clone();
+        for _ in 0..depth {
+            s = s
+                .chars()
+                .map(|c| match self.rules.get(&c) {
+                    Some(r) => r.clone(),
+                    None => c.to_string(),
+                })
+                .collect();
+        }
+        s
+    }
+
+    /// Walks the turtle over `commands` and returns the visited points.
+    ///
+    /// `heading` is the initial direction in degrees, counterclockwise from the x axis.
+    pub fn interpret(
+        &self,
+        commands: &str,
+        start: &Point<f64>,
+        step: f64,
+        heading: f64,
+    ) -> Vec<Point<f64>> {
+        let mut v = vec![*start];
+        let mut position = *start;
+        let mut heading = heading;
+
+        for c in commands.chars() {
+            match c {
+                '+' => heading += self.angle,
+                '-' => heading -= self.angle,
+                '|' => heading += 180.,
+                c if self.draw.contains(c) => {
+                    let (sin, cos) = heading.to_radians().sin_cos();
+                    position = Point::new(position.x + step * cos, position.y + step * sin);
+                    v.push(position);
+                }
+                _ => {}
+            }
+        }
+
+        v
+    }
+
+    pub fn compute(
+        &self,
+        depth: usize,
+        start: &Point<f64>,
+        step: f64,
+        heading: f64,
+    ) -> Vec<Point<f64>> {
+        self.interpret(&self.expand(depth), start, step, heading)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::recursive::koch_curve;
+
+    fn origin() -> Point<f64> {
+        Point::new(0., 0.)
+    }
+
+    fn assert_close(a: &Point<f64>, b: &Point<f64>) {
+        assert!(
+            (a.x - b.x).abs() < 1e-6 && (a.y - b.y).abs() < 1e-6,
+            "{:?} {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn test_expand() {
+        let l = LSystem::dragon_curve();
+        assert_eq!(l.expand(0), "FX");
+        assert_eq!(l.expand(2), "FX+YF++-FX-YF+");
+    }
+
+    #[test]
+    fn test_koch_curve() {
+        for depth in 0..5 {
+            let step = 100. / 3f64.powi(depth as i32);
+            let v = LSystem::koch_curve().compute(depth, &origin(), step, 0.);
+            let expected = koch_curve::compute(&origin(), &Point::new(100., 0.), depth);
+
+            assert_eq!(v.len(), expected.len());
+            for (a, b) in v.iter().zip(&expected) {
+                assert_close(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sierpinski_triangle() {
+        let v = LSystem::sierpinski_triangle().compute(3, &origin(), 1., 0.);
+
+        assert_eq!(v.len(), 3usize.pow(4) + 1);
+        assert_close(v.last().unwrap(), &origin());
+    }
+
+    #[test]
+    fn test_hilbert_curve() {
+        let depth = 3;
+        let v = LSystem::hilbert_curve().compute(depth, &origin(), 1., 0.);
+        let side = 2i64.pow(depth as u32);
+
+        let mut cells: Vec<(i64, i64)> = v
+            .iter()
+            .map(|p| (p.x.round() as i64, p.y.round() as i64))
+            .collect();
+        assert_eq!(cells.len(), (side * side) as usize);
+        cells.sort_unstable();
+        cells.dedup();
+        assert_eq!(cells.len(), (side * side) as usize);
+        assert!(cells.iter().all(|&(x, y)| x.abs() < side && y.abs() < side));
+    }
+
+    #[test]
+    fn test_dragon_and_levy_c_curve() {
+        let v = LSystem::dragon_curve().compute(10, &origin(), 1., 0.);
+        assert_eq!(v.len(), 2usize.pow(10) + 1);
+        // each iteration scales the end point by sqrt(2) and rotates it by 45 degrees
+        assert_close(v.last().unwrap(), &Point::new(0., 32.));
+
+        let depth = 8;
+        let step = 0.5f64.sqrt().powi(depth as i32);
+        let v = LSystem::levy_c_curve().compute(depth, &origin(), step, 0.);
+        assert_eq!(v.len(), 2usize.pow(depth as u32) + 1);
+        assert_close(v.last().unwrap(), &Point::new(1., 0.));
+    }
+}
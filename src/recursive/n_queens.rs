@@ -0,0 +1,293 @@
+use super::backtracking::{self, Backtrack};
+use std::{
+    error::Error,
+    io::{prelude::*, BufReader},
+};
+
+/// Queens placed row by row on an `n x n` board so that none attacks another,
+/// keeping any queens placed beforehand.
+#[derive(Debug, Clone)]
+pub struct Queens {
+    n: usize,
+    /// Column of the queen placed beforehand on every row, if any.
+    fixed: Vec<Option<usize>>,
+    /// Columns of the queens of the rows filled so far.
+    columns: Vec<usize>,
+    used_columns: Vec<bool>,
+    /// Indexed by `row + col`.
+    used_diagonals: Vec<bool>,
+    /// Indexed by `row + n - 1 - col`.
+    used_anti_diagonals: Vec<bool>,
+    /// Whether the queens placed beforehand attack each other.
+    conflict: bool,
+}
+
+impl Queens {
+    pub fn new(n: usize) -> Self {
+        Self::with_queens(n, &[])
+    }
+
+    /// A board with queens already at the given `(row, col)` cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a cell is off the board.
+    pub fn with_queens(n: usize, queens: &[(usize, usize)]) -> Self {
+        let mut result = Queens {
+            n,
+            fixed: vec![None; n],
+            columns: Vec::with_capacity(n),
+            used_columns: vec![false; n],
+            used_diagonals: vec![false; (2 * n).saturating_sub(1)],
+            used_anti_diagonals: vec![false; (2 * n).saturating_sub(1)],
+            conflict: false,
+        };
+        for &(row, col) in queens {
+            assert!(row < n && col < n, "queen off the board");
+            if result.fixed[row].is_some() || result.is_attacked(row, col) {
+                result.conflict = true;
+            } else {
+                result.fixed[row] = Some(col);
+                result.mark(row, col, true);
+            }
+        }
+        result
+    }
+
+    fn is_attacked(&self, row: usize, col: usize) -> bool {
+        self.used_columns[col]
+            || self.used_diagonals[row + col]
+            || self.used_anti_diagonals[row + self.n - 1 - col]
+    }
+
+    fn mark(&mut self, row: usize, col: usize, used: bool) {
+        self.used_columns[col] = used;
+        self.used_diagonals[row + col] = used;
+        self.used_anti_diagonals[row + self.n - 1 - col] = used;
+    }
+}
+
+impl Backtrack for Queens {
+    type Choice = usize;
+    /// Column of the queen on every row.
+    type Solution = Vec<usize>;
+
+    fn is_complete(&self) -> bool {
+        self.columns.len() == self.n
+    }
+
+    fn choices(&self) -> Vec<usize> {
+        match self.fixed[self.columns.len()] {
+            Some(col) => vec![col],
+            None => (0..self.n).collect(),
+        }
+    }
+
+    fn is_promising(&self) -> bool {
+        !self.conflict
+    }
+
+    fn is_feasible(&self, &col: &usize) -> bool {
+        let row = self.columns.len();
+        self.fixed[row] == Some(col) || !self.is_attacked(row, col)
+    }
+
+    fn choose(&mut self, &col: &usize) {
+        let row = self.columns.len();
+        if self.fixed[row].is_none() {
+            self.mark(row, col, true);
+        }
+        self.columns.push(col);
+    }
+
+    fn unchoose(&mut self, &col: &usize) {
+        self.columns.pop();
+        let row = self.columns.len();
+        if self.fixed[row].is_none() {
+            self.mark(row, col, false);
+        }
+    }
+
+    fn solution(&self) -> Vec<usize> {
+        self.columns.clone()
+    }
+}
+
+/// Number of ways to place `n` queens, tracking the attacked columns and
+/// diagonals of the next row as bitmasks.
+///
+/// # Panics
+///
+/// Panics if `n` is 64 or more, as the board rows are `u64` masks.
+pub fn count_queens(n: usize) -> usize {
+    fn inner(all: u64, columns: u64, left: u64, right: u64) -> usize {
+        if columns == all {
+            return 1;
+        }
+        let mut free = all & !(columns | left | right);
+        let mut count = 0;
+        while free != 0 {
+            let bit = free & free.wrapping_neg();
+            free ^= bit;
+            count += inner(
+                all,
+                columns | bit,
+                (left | bit) << 1 & all,
+                (right | bit) >> 1,
+            );
+        }
+        count
+    }
+
+    assert!(n < 64, "bitmask queens support up to 63 columns");
+    inner((1 << n) - 1, 0, 0, 0)
+}
+
+/// The first placement of `n` queens found with the bitmasks of `count_queens`,
+/// as the column of the queen on every row.
+///
+/// # Panics
+///
+/// Panics if `n` is 64 or more, as the board rows are `u64` masks.
+pub fn first_queens(n: usize) -> Option<Vec<usize>> {
+    fn inner(all: u64, columns: u64, left: u64, right: u64, result: &mut Vec<usize>) -> bool {
+        if columns == all {
+            return true;
+        }
+        let mut free = all & !(columns | left | right);
+        while free != 0 {
+            let bit = free & free.wrapping_neg();
+            free ^= bit;
+            result.push(bit.trailing_zeros() as usize);
+            if inner(
+                all,
+                columns | bit,
+                (left | bit) << 1 & all,
+                (right | bit) >> 1,
+                result,
+            ) {
+                return true;
+            }
+            result.pop();
+        }
+        false
+    }
+
+    assert!(n < 64, "bitmask queens support up to 63 columns");
+    let mut result = Vec::with_capacity(n);
+    if inner((1 << n) - 1, 0, 0, 0, &mut result) {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+/// The board with `Q` for a queen and `.` for an empty cell, one row per line.
+pub fn render(columns: &[usize]) -> Vec<String> {
+    let n = columns.len();
+    columns
+        .iter()
+        .map(|&col| (0..n).map(|c| if c == col { 'Q' } else { '.' }).collect())
+        .collect()
+}
+
+/// ALDS1_13_A: `k` and `k` queens as `r c`; prints the 8-queens board that
+/// keeps them.
+pub fn input_8_queens(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let k: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut queens = Vec::with_capacity(k);
+    for line in lines.take(k) {
+        let values = line?
+            .split_whitespace()
+            .map(|x| x.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()?;
+        if values[0] >= 8 || values[1] >= 8 {
+            return Err("queen off the board".into());
+        }
+        queens.push((values[0], values[1]));
+    }
+
+    let columns = backtracking::first(&mut Queens::with_queens(8, &queens)).ok_or("no solution")?;
+    for line in render(&columns) {
+        writeln!(writer, "{}", line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let input = ["2", "2 2", "5 3"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_8_queens(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "......Q.", "Q.......", "..Q.....", ".......Q", ".....Q..", "...Q....", ".Q......",
+                "....Q...", ""
+            ]
+            .join("\n")
+        );
+    }
+
+    fn assert_valid(columns: &[usize]) {
+        for (r1, &c1) in columns.iter().enumerate() {
+            for (r2, &c2) in columns.iter().enumerate().skip(r1 + 1) {
+                assert_ne!(c1, c2);
+                assert_ne!(r2 - r1, c1.max(c2) - c1.min(c2));
+            }
+        }
+    }
+
+    #[test]
+    fn test_count() {
+        let expected = [1, 1, 0, 0, 2, 10, 4, 40, 92, 352];
+        for (n, &expected) in expected.iter().enumerate() {
+            assert_eq!(count_queens(n), expected);
+            assert_eq!(backtracking::count(&mut Queens::new(n)), expected);
+
+            // both try the columns in ascending order
+            let first = first_queens(n);
+            assert_eq!(first, backtracking::first(&mut Queens::new(n)));
+            assert_eq!(first.is_some(), expected > 0);
+            if let Some(columns) = first {
+                assert_eq!(columns.len(), n);
+                assert_valid(&columns);
+            }
+        }
+        assert_eq!(count_queens(12), 14200);
+    }
+
+    #[test]
+    fn test_fixed() {
+        let mut queens = Queens::with_queens(8, &[(2, 2), (5, 3)]);
+        let solutions = backtracking::all(&mut queens);
+        assert_eq!(solutions, vec![vec![6, 0, 2, 7, 5, 3, 1, 4]]);
+
+        // every placement of 8 queens keeps its own queen on row 0
+        let total: usize = (0..8)
+            .map(|col| backtracking::count(&mut Queens::with_queens(8, &[(0, col)])))
+            .sum();
+        assert_eq!(total, 92);
+
+        assert_eq!(
+            backtracking::count(&mut Queens::with_queens(8, &[(0, 0), (1, 1)])),
+            0
+        );
+        assert_eq!(
+            backtracking::count(&mut Queens::with_queens(8, &[(3, 0), (3, 5)])),
+            0
+        );
+    }
+}
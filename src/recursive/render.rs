@@ -0,0 +1,227 @@
+use super::koch_curve::Point;
+use std::{error::Error, io::prelude::*};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    /// Width of the viewport, in pixels for SVG and points for EPS.
+    pub width: f64,
+    /// Height of the viewport, in pixels for SVG and points for EPS.
+    pub height: f64,
+    pub margin: f64,
+    /// Viewport units per curve unit. `None` fits the curve into the viewport.
+    pub scale: Option<f64>,
+    /// Stroke colour as RGB.
+    pub stroke: [u8; 3],
+    pub stroke_width: f64,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            width: 400.,
+            height: 400.,
+            margin: 10.,
+            scale: None,
+            stroke: [0, 0, 0],
+            stroke_width: 1.,
+        }
+    }
+}
+
+/// Maps curve coordinates into the viewport, centring the bounding box of `points`.
+/// The y axis points up in the returned coordinates.
+fn transform(points: &[Point<f64>], options: &RenderOptions) -> Vec<(f64, f64)> {
+    if points.is_empty() {
+        return vec![];
+    }
+
+    let fold = |f: fn(f64, f64) -> f64, init: f64, get: fn(&Point<f64>) -> f64| {
+        points.iter().map(get).fold(init, f)
+    };
+    let min_x = fold(f64::min, f64::INFINITY, |p| p.x);
+    let max_x = fold(f64::max, f64::NEG_INFINITY, |p| p.x);
+    let min_y = fold(f64::min, f64::INFINITY, |p| p.y);
+    let max_y = fold(f64::max, f64::NEG_INFINITY, |p| p.y);
+
+    let scale = options.scale.unwrap_or_else(|| {
+        let fit = |available: f64, extent: f64| {
+            if extent > 0. {
+                available / extent
+            } else {
+                f64::INFINITY
+            }
+        };
+        let scale = fit(options.width - options.margin * 2., max_x - min_x)
+            .min(fit(options.height - options.margin * 2., max_y - min_y));
+        if scale.is_finite() {
+            scale
+        } else {
+            1.
+        }
+    });
+
+    let offset_x = (options.width - (max_x - min_x) * scale) / 2.;
+    let offset_y = (options.height - (max_y - min_y) * scale) / 2.;
+    points
+        .iter()
+        .map(|p| {
+            (
+                offset_x + (p.x - min_x) * scale,
+                offset_y + (p.y - min_y) * scale,
+            )
+        })
+        .collect()
+}
+
+pub fn write_svg(
+    points: &[Point<f64>],
+    options: &RenderOptions,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let [r, g, b] = options.stroke;
+    let points: Vec<String> = transform(points, options)
+        .into_iter()
+        // SVG's y axis points down
+        .map(|(x, y)| format!("{:.3},{:.3}", x, options.height - y))
+        .collect();
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = options.width,
+        h = options.height
+    )?;
+    writeln!(
+        writer,
+        r##"<polyline points="{}" fill="none" stroke="#{:02x}{:02x}{:02x}" stroke-width="{}"/>"##,
+        points.join(" "),
+        r,
+        g,
+        b,
+        options.stroke_width
+    )?;
+    writeln!(writer, "</svg>")?;
+
+    Ok(())
+}
+
+pub fn write_eps(
+    points: &[Point<f64>],
+    options: &RenderOptions,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let [r, g, b] = options.stroke;
+    let points = transform(points, options);
+
+    writeln!(writer, "%!PS-Adobe-3.0 EPSF-3.0")?;
+    writeln!(
+        writer,
+        "%%BoundingBox: 0 0 {} {}",
+        options.width.ceil(),
+        options.height.ceil()
+    )?;
+    writeln!(writer, "%%EndComments")?;
+    writeln!(
+        writer,
+        "{:.3} {:.3} {:.3} setrgbcolor",
+        f64::from(r) / 255.,
+        f64::from(g) / 255.,
+        f64::from(b) / 255.
+    )?;
+    writeln!(writer, "{} setlinewidth", options.stroke_width)?;
+    writeln!(writer, "newpath")?;
+    for (i, (x, y)) in points.into_iter().enumerate() {
+        let op = if i == 0 { "moveto" } else { "lineto" };
+        writeln!(writer, "{:.3} {:.3} {}", x, y, op)?;
+    }
+    writeln!(writer, "stroke")?;
+    writeln!(writer, "showpage")?;
+    writeln!(writer, "%%EOF")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::recursive::koch_curve::compute;
+
+    fn line() -> Vec<Point<f64>> {
+        vec![Point { x: 0., y: 0. }, Point { x: 100., y: 50. }]
+    }
+
+    #[test]
+    fn test_svg() {
+        let options = RenderOptions {
+            width: 120.,
+            height: 70.,
+            stroke: [255, 0, 16],
+            stroke_width: 0.5,
+            ..Default::default()
+        };
+        let mut output: Vec<u8> = vec![];
+
+        let result = write_svg(&line(), &options, &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="120" height="70" viewBox="0 0 120 70">"#,
+                r##"<polyline points="10.000,60.000 110.000,10.000" fill="none" stroke="#ff0010" stroke-width="0.5"/>"##,
+                "</svg>",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_eps() {
+        let options = RenderOptions {
+            width: 300.,
+            height: 300.,
+            scale: Some(2.),
+            ..Default::default()
+        };
+        let mut output: Vec<u8> = vec![];
+
+        let result = write_eps(&line(), &options, &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            [
+                "%!PS-Adobe-3.0 EPSF-3.0",
+                "%%BoundingBox: 0 0 300 300",
+                "%%EndComments",
+                "0.000 0.000 0.000 setrgbcolor",
+                "1 setlinewidth",
+                "newpath",
+                "50.000 100.000 moveto",
+                "250.000 200.000 lineto",
+                "stroke",
+                "showpage",
+                "%%EOF",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_fit_koch_curve() {
+        let points = compute(&Point { x: 0., y: 0. }, &Point { x: 3., y: 0. }, 3);
+        let options = RenderOptions::default();
+
+        let transformed = transform(&points, &options);
+
+        assert_eq!(transformed.len(), points.len());
+        for &(x, y) in &transformed {
+            assert!(x >= options.margin - 1e-9 && x <= options.width - options.margin + 1e-9);
+            assert!(y >= 0. && y <= options.height);
+        }
+        assert!((transformed[0].0 - options.margin).abs() < 1e-9);
+        assert!((transformed.last().unwrap().0 - (options.width - options.margin)).abs() < 1e-9);
+    }
+}
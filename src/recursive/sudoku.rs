@@ -0,0 +1,148 @@
+use super::backtracking::Backtrack;
+
+/// Rows of digits 1 to 9, with 0 for an empty cell.
+pub type Grid = [[u8; 9]; 9];
+
+/// A Sudoku filled by always branching on the empty cell with the fewest
+/// candidates.
+#[derive(Debug, Clone)]
+pub struct Sudoku {
+    grid: Grid,
+    /// Bit `d` is set if digit `d` is used in the row, column or box.
+    rows: [u16; 9],
+    columns: [u16; 9],
+    boxes: [u16; 9],
+    empty: usize,
+    /// Whether the givens break a rule.
+    conflict: bool,
+}
+
+impl Sudoku {
+    /// # Panics
+    ///
+    /// Panics if a cell holds a value above 9.
+    pub fn new(grid: Grid) -> Self {
+        let mut result = Sudoku {
+            grid: [[0; 9]; 9],
+            rows: [0; 9],
+            columns: [0; 9],
+            boxes: [0; 9],
+            empty: 81,
+            conflict: false,
+        };
+        for (row, line) in grid.iter().enumerate() {
+            for (col, &digit) in line.iter().enumerate() {
+                assert!(digit <= 9, "invalid digit {}", digit);
+                if digit == 0 {
+                    continue;
+                }
+                if result.candidates(row, col) & 1 << digit == 0 {
+                    result.conflict = true;
+                }
+                result.choose(&(row, col, digit));
+            }
+        }
+        result
+    }
+
+    /// Digits still allowed at a cell as a bitmask.
+    fn candidates(&self, row: usize, col: usize) -> u16 {
+        !(self.rows[row] | self.columns[col] | self.boxes[row / 3 * 3 + col / 3]) & 0b11_1111_1110
+    }
+}
+
+impl Backtrack for Sudoku {
+    /// Puts digit `.2` at row `.0` and column `.1`.
+    type Choice = (usize, usize, u8);
+    type Solution = Grid;
+
+    fn is_complete(&self) -> bool {
+        self.empty == 0
+    }
+
+    fn choices(&self) -> Vec<(usize, usize, u8)> {
+        let cell = (0..81)
+            .map(|i| (i / 9, i % 9))
+            .filter(|&(row, col)| self.grid[row][col] == 0)
+            .min_by_key(|&(row, col)| self.candidates(row, col).count_ones());
+        match cell {
+            Some((row, col)) => {
+                let candidates = self.candidates(row, col);
+                (1..=9)
+                    .filter(|&digit| candidates & 1 << digit != 0)
+                    .map(|digit| (row, col, digit))
+                    .collect()
+            }
+            None => vec![],
+        }
+    }
+
+    fn is_promising(&self) -> bool {
+        !self.conflict
+    }
+
+    fn choose(&mut self, &(row, col, digit): &(usize, usize, u8)) {
+        self.grid[row][col] = digit;
+        self.rows[row] |= 1 << digit;
+        self.columns[col] |= 1 << digit;
+        self.boxes[row / 3 * 3 + col / 3] |= 1 << digit;
+        self.empty -= 1;
+    }
+
+    fn unchoose(&mut self, &(row, col, digit): &(usize, usize, u8)) {
+        self.grid[row][col] = 0;
+        self.rows[row] &= !(1 << digit);
+        self.columns[col] &= !(1 << digit);
+        self.boxes[row / 3 * 3 + col / 3] &= !(1 << digit);
+        self.empty += 1;
+    }
+
+    fn solution(&self) -> Grid {
+        self.grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::recursive::backtracking::{count, first};
+
+    fn grid(digits: &str) -> Grid {
+        let mut grid = [[0; 9]; 9];
+        for (i, c) in digits.chars().enumerate() {
+            grid[i / 9][i % 9] = c.to_digit(10).unwrap() as u8;
+        }
+        grid
+    }
+
+    #[test]
+    fn test_solve() {
+        let puzzle =
+            "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+        let solved =
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+        assert_eq!(first(&mut Sudoku::new(grid(puzzle))), Some(grid(solved)));
+        assert_eq!(count(&mut Sudoku::new(grid(puzzle))), 1);
+        assert_eq!(count(&mut Sudoku::new(grid(solved))), 1);
+
+        // a 6/7 rectangle over two boxes can be filled either way
+        let mut two = grid(solved);
+        for &(row, col) in &[(0, 3), (0, 4), (3, 3), (3, 4)] {
+            two[row][col] = 0;
+        }
+        assert_eq!(count(&mut Sudoku::new(two)), 2);
+    }
+
+    #[test]
+    fn test_conflict() {
+        let mut broken = [[0; 9]; 9];
+        broken[0][0] = 5;
+        broken[4][0] = 5;
+        assert_eq!(first(&mut Sudoku::new(broken)), None);
+
+        // no digit fits the top-left cell
+        let blocked =
+            "012345678900000000000000000000000000000000000000000000000000000000000000000000000";
+        assert_eq!(count(&mut Sudoku::new(grid(blocked))), 0);
+    }
+}
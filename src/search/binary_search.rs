@@ -5,6 +5,14 @@ use std::{
 };
 pub trait BinarySearch<T, TIndex> {
     fn binary_search2(&self, value: &T) -> Option<TIndex>;
+
+    /// First index whose element is not less than `value` in a sorted sequence,
+    /// or the length if there is none.
+    fn lower_bound(&self, value: &T) -> TIndex;
+
+    /// First index whose element is greater than `value` in a sorted sequence,
+    /// or the length if there is none.
+    fn upper_bound(&self, value: &T) -> TIndex;
 }
 
 impl<T: PartialEq + PartialOrd> BinarySearch<T, usize> for [T] {
@@ -17,6 +25,28 @@ impl<T: PartialEq + PartialOrd> BinarySearch<T, usize> for [T] {
             _ => None,
         }
     }
+
+    fn lower_bound(&self, value: &T) -> usize {
+        partition_point(self, |x| x < value)
+    }
+
+    fn upper_bound(&self, value: &T) -> usize {
+        partition_point(self, |x| x <= value)
+    }
+}
+
+/// First index for which `pred` is false, given that it holds on a prefix.
+fn partition_point<T>(seq: &[T], pred: impl Fn(&T) -> bool) -> usize {
+    let (mut low, mut high) = (0, seq.len());
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if pred(&seq[middle]) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    low
 }
 
 pub fn input_binary_search(
@@ -76,4 +106,17 @@ mod test {
         assert!(result.is_ok());
         assert_eq!(String::from_utf8(output).unwrap(), "3");
     }
+
+    #[test]
+    fn test_bounds() {
+        let seq = [1, 2, 2, 2, 5, 7];
+        let bounds = [0, 1, 2, 5, 7, 8]
+            .iter()
+            .map(|x| (seq.lower_bound(x), seq.upper_bound(x)))
+            .collect::<Vec<_>>();
+        assert_eq!(bounds, vec![(0, 0), (0, 1), (1, 4), (4, 5), (5, 6), (6, 6)]);
+
+        let empty: [usize; 0] = [];
+        assert_eq!((empty.lower_bound(&3), empty.upper_bound(&3)), (0, 0));
+    }
 }
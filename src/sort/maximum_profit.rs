@@ -1,24 +1,144 @@
+use std::{
+    error::Error,
+    io::{prelude::*, BufReader},
+    ops::{Add, Sub},
+};
+
 type Int = isize;
 
-pub fn get_max_profit(mut input: impl Iterator<Item = Int>) -> Option<Int> {
-    let len = input.next().unwrap_or_else(|| 0);
+/// A single buy at `buy` followed by a sell at `sell`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Trade<T> {
+    pub buy: usize,
+    pub sell: usize,
+    pub profit: T,
+}
+
+/// Best `prices[sell] - prices[buy]` with `buy < sell`. The profit is negative when
+/// prices only fall. Returns `None` for fewer than two prices.
+pub fn get_max_profit<T>(input: impl IntoIterator<Item = T>) -> Option<Trade<T>>
+where
+    T: Copy + PartialOrd + Sub<Output = T>,
+{
+    let mut input = input.into_iter().enumerate();
+    let (mut min_index, mut min) = input.next()?;
 
-    if [0, 1].contains(&len) {
-        return None;
+    let mut result: Option<Trade<T>> = None;
+    for (i, r) in input {
+        let profit = r - min;
+        match result {
+            Some(cur) if cur.profit >= profit => {}
+            _ => {
+                result = Some(Trade {
+                    buy: min_index,
+                    sell: i,
+                    profit,
+                })
+            }
+        }
+        if r < min {
+            min = r;
+            min_index = i;
+        }
     }
 
-    let mut min: Int;
-    min = input.next().unwrap();
+    result
+}
+
+/// A non-empty range `start..end` and its sum.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Subarray<T> {
+    pub start: usize,
+    pub end: usize,
+    pub sum: T,
+}
 
-    let mut result: Option<Int> = None;
+/// Kadane's algorithm. Returns `None` for an empty slice.
+pub fn max_subarray<T>(seq: &[T]) -> Option<Subarray<T>>
+where
+    T: Copy + PartialOrd + Add<Output = T>,
+{
+    let first = *seq.first()?;
+    let mut best = Subarray {
+        start: 0,
+        end: 1,
+        sum: first,
+    };
+    // best subarray ending at the current index
+    let (mut start, mut sum) = (0, first);
 
-    for r in input {
-        let dif = r - min;
-        result = result.map_or(dif, |cur| cur.max(dif)).into();
-        min = min.min(r);
+    for (i, &x) in seq.iter().enumerate().skip(1) {
+        if sum + x > x {
+            sum = sum + x;
+        } else {
+            start = i;
+            sum = x;
+        }
+        if sum > best.sum {
+            best = Subarray {
+                start,
+                end: i + 1,
+                sum,
+            };
+        }
     }
 
-    result
+    Some(best)
+}
+
+/// Best total profit with at most `k` non-overlapping trades.
+pub fn max_profit_k(prices: &[Int], k: usize) -> Int {
+    if k >= prices.len() / 2 {
+        // as good as unlimited trades
+        return prices.windows(2).map(|w| (w[1] - w[0]).max(0)).sum();
+    }
+
+    // hold[j]: best balance holding a share during the j-th trade
+    // free[j]: best balance after completing j trades
+    let mut hold = vec![Int::MIN; k + 1];
+    let mut free = vec![0; k + 1];
+    for &p in prices {
+        for j in 1..=k {
+            hold[j] = hold[j].max(free[j - 1] - p);
+            free[j] = free[j].max(hold[j].saturating_add(p));
+        }
+    }
+
+    free[k]
+}
+
+/// Best total profit with unlimited trades when every sell is followed by a
+/// one-day cooldown.
+pub fn max_profit_with_cooldown(prices: &[Int]) -> Int {
+    let mut hold = Int::MIN;
+    let mut sold = 0;
+    let mut rest = 0;
+    for &p in prices {
+        let prev_sold = sold;
+        sold = hold.saturating_add(p);
+        hold = hold.max(rest - p);
+        rest = rest.max(prev_sold);
+    }
+
+    sold.max(rest)
+}
+
+pub fn input_maximum_profit(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    let mut lines = reader.lines();
+    let len: usize = lines.next().unwrap()?.trim().parse()?;
+    let mut prices: Vec<Int> = Vec::with_capacity(len);
+    for line in lines.take(len) {
+        prices.push(line?.trim().parse()?);
+    }
+
+    let result = get_max_profit(prices).ok_or("at least two prices are required")?;
+    writeln!(writer, "{}", result.profit)?;
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -27,14 +147,78 @@ mod test {
 
     #[test]
     fn test1() {
-        let input = vec![6, 5, 3, 1, 3, 4, 3];
+        let input = vec![5, 3, 1, 3, 4, 3];
 
-        assert_eq!(Some(3), get_max_profit(input.into_iter()));
+        assert_eq!(
+            Some(Trade {
+                buy: 2,
+                sell: 4,
+                profit: 3
+            }),
+            get_max_profit(input)
+        );
     }
 
     #[test]
     fn test2() {
-        let input = vec![3, 4, 3, 2];
-        assert_eq!(Some(-1), get_max_profit(input.into_iter()));
+        let input = [4, 3, 2];
+        assert_eq!(
+            Some(Trade {
+                buy: 0,
+                sell: 1,
+                profit: -1
+            }),
+            get_max_profit(input.iter().copied())
+        );
+        assert_eq!(None, get_max_profit(vec![1]));
+    }
+
+    #[test]
+    fn test3() {
+        let input = ["6", "5", "3", "1", "3", "4", "3"].join("\n");
+        let mut output: Vec<u8> = vec![];
+
+        let result = input_maximum_profit(&mut input.as_bytes(), &mut output);
+
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(output).unwrap(), "3\n");
+    }
+
+    #[test]
+    fn test_max_subarray() {
+        assert_eq!(
+            max_subarray(&[-2, 1, -3, 4, -1, 2, 1, -5, 4]),
+            Some(Subarray {
+                start: 3,
+                end: 7,
+                sum: 6
+            })
+        );
+        assert_eq!(
+            max_subarray(&[-3, -1, -2]),
+            Some(Subarray {
+                start: 1,
+                end: 2,
+                sum: -1
+            })
+        );
+        assert_eq!(max_subarray::<Int>(&[]), None);
+    }
+
+    #[test]
+    fn test_max_profit_k() {
+        assert_eq!(max_profit_k(&[2, 4, 1], 2), 2);
+        assert_eq!(max_profit_k(&[3, 2, 6, 5, 0, 3], 2), 7);
+        assert_eq!(max_profit_k(&[3, 2, 6, 5, 0, 3], 1), 4);
+        assert_eq!(max_profit_k(&[1, 2, 4, 2, 5, 7, 2, 4, 9, 0], 2), 13);
+        assert_eq!(max_profit_k(&[5, 4, 3], 1), 0);
+        assert_eq!(max_profit_k(&[1, 5], 0), 0);
+    }
+
+    #[test]
+    fn test_max_profit_with_cooldown() {
+        assert_eq!(max_profit_with_cooldown(&[1, 2, 3, 0, 2]), 3);
+        assert_eq!(max_profit_with_cooldown(&[1]), 0);
+        assert_eq!(max_profit_with_cooldown(&[]), 0);
     }
 }